@@ -5,9 +5,15 @@
 
 mod state;
 
-use doodle_game::{Operation, DoodleGameAbi, Player, PlayerStatus, GameState, ChatMessage, ArchivedRoom, CrossChainMessage, Invitation};
+use std::str::FromStr;
+
+use doodle_game::{Operation, DoodleGameAbi, DoodleParameters, Player, PlayerStatus, GameState, GameRoom, ChatMessage, ArchivedRoom, CrossChainMessage, Invitation, JoinApplication, JoinRejectReason, PendingJoin, AuditStatus, GuessValidation, GuessCheck, RoomSettings, TurnEndReason, TurnOutcome, WordTimeoutAction, DoodleError, RejectedEvents};
+use doodle_game::guess::{self, GuessVerdict};
+use doodle_game::hint::{self, WordHint};
+use doodle_game::registry::{self, MatchTicket, RoomListing};
+use doodle_game::word_bank::{self, WordBank};
 use linera_sdk::{
-    linera_base_types::{WithContractAbi, StreamName, ChainId},
+    linera_base_types::{WithContractAbi, ChainId, CryptoHash, DataBlobHash, TimeDelta},
     views::{RootView, View},
     Contract, ContractRuntime,
 };
@@ -29,18 +35,1250 @@ impl WithContractAbi for DoodleGameContract {
 impl DoodleGameContract {
     /// Subscribe to ALL players when they join (host only)
     /// Events will be filtered by current_drawer_index in process_streams
-    fn subscribe_to_player(&mut self, player_chain_id: &str) {
+    fn subscribe_to_player(&mut self, player_chain: ChainId) {
         let current_chain = self.runtime.chain_id();
         if let Some(room) = self.state.room.get() {
             // Only host subscribes to players
-            if room.host_chain_id == current_chain.to_string() {
-                if let Ok(player_chain) = player_chain_id.parse() {
+            if room.host_chain_id == current_chain {
+                let app_id = self.runtime.application_id().forget_abi();
+                eprintln!("[SUBSCRIPTION] Host subscribing to player chain {:?}", player_chain);
+                self.runtime.subscribe_to_events(player_chain, app_id, room.stream_name());
+                eprintln!("[SUBSCRIPTION] Subscribed to player events");
+            }
+        }
+    }
+
+    /// Resolve the words for these settings: the custom pack blob if set, otherwise a built-in bank
+    fn load_word_bank(&mut self, settings: &RoomSettings) -> Result<WordBank, String> {
+        match &settings.word_pack {
+            Some(hash) => {
+                let crypto_hash = CryptoHash::from_str(hash)
+                    .map_err(|e| format!("Invalid word pack hash '{}': {:?}", hash, e))?;
+                let bytes = self.runtime.read_data_blob(DataBlobHash(crypto_hash));
+                word_bank::parse_word_pack(hash, &bytes)
+            }
+            None => word_bank::builtin_bank(settings.word_bank_id())
+                .ok_or_else(|| format!("Unknown word bank '{}'", settings.word_bank_id())),
+        }
+    }
+
+    /// Check that the settings' word bank or pack exists and has the chosen category
+    fn check_settings(&mut self, settings: &RoomSettings) -> Result<(), DoodleError> {
+        let bank = self.load_word_bank(settings)
+            .and_then(|bank| settings.validate(&bank).map(|()| bank))
+            .map_err(DoodleError::InvalidSettings)?;
+        if settings.word_pack.is_some() {
+            self.remember_word_pack(&bank);
+        }
+        Ok(())
+    }
+
+    /// Remember a validated custom pack so the service can list it
+    fn remember_word_pack(&mut self, bank: &WordBank) {
+        let mut packs = self.state.word_packs.get().clone();
+        if !packs.iter().any(|pack| pack.id == bank.id) {
+            packs.push(bank.summary());
+            self.state.word_packs.set(packs);
+        }
+    }
+
+    /// Reset per-turn local state for a new drawer: offer word candidates if this chain
+    /// draws, and forget last turn's private close-guess feedback
+    fn prepare_local_turn(&mut self, room: &GameRoom) {
+        self.state.close_guesses.set(Vec::new());
+        // A salt reserved for a turn that was skipped must not be reused (a pending reveal keeps its own)
+        if self.state.current_word.get().is_none() {
+            self.state.word_salt.set(None);
+        }
+        
+        let current_chain = self.runtime.chain_id();
+        let is_drawer = room.get_current_drawer()
+            .map_or(false, |drawer| drawer.chain_id == current_chain);
+        
+        let candidates = if is_drawer {
+            match self.load_word_bank(&room.settings) {
+                Ok(bank) => room.word_candidates(&bank, &self.runtime.system_time().micros().to_string()),
+                Err(error) => {
+                    eprintln!("[WORD_CANDIDATES] ERROR: {}", error);
+                    Vec::new()
+                }
+            }
+        } else {
+            Vec::new()
+        };
+        
+        if is_drawer {
+            eprintln!("[WORD_CANDIDATES] Offered {} candidates to this drawer", candidates.len());
+        }
+        self.state.word_candidates.set(candidates);
+    }
+
+    /// Flag this player's own chat messages that the scorer reported as close
+    fn mark_own_close_guesses(&self, room: &mut GameRoom) {
+        let current_chain = self.runtime.chain_id();
+        room.mark_close_guesses(current_chain, self.state.close_guesses.get());
+    }
+
+    /// Drawer only: publish the letter hint due at the current time if it changed
+    fn publish_due_hint(&mut self, room: &mut GameRoom) {
+        let Some(word) = self.state.current_word.get().clone() else {
+            return;
+        };
+        let Some(chosen_at) = room.word_chosen_at else {
+            return;
+        };
+        
+        let now = self.runtime.system_time();
+        let revealed = hint::due_reveals(&word, now.delta_since(chosen_at).as_micros(), room.seconds_per_round);
+        if room.word_hint.as_ref().map_or(false, |current| current.revealed_letters >= revealed) {
+            return;
+        }
+        
+        // Seed letter order with the commitment so every hint in a turn extends the previous one
+        let seed = room.current_turn.as_ref().map(|turn| turn.commitment.clone()).unwrap_or_default();
+        let hint = WordHint {
+            mask: hint::mask_word(&word, revealed, &seed),
+            revealed_letters: revealed,
+            timestamp: now,
+        };
+        let event = doodle_game::DoodleEvent::HintRevealed { room_id: room.room_id.clone(), hint };
+        match self.publish(room, event) {
+            Ok(()) => eprintln!("[HINT] Published hint with {} revealed letters", revealed),
+            Err(error) => eprintln!("[HINT] ERROR: {}", error),
+        }
+    }
+
+    /// Whether `source` may send us this event
+    /// Room control comes only from the host; drawer events from the current drawer or the host relaying them
+    fn is_trusted_source(room: &GameRoom, source: ChainId, event: &doodle_game::DoodleEvent) -> bool {
+        let is_host = room.host_chain_id == source;
+        let is_drawer = room.get_current_drawer().map_or(false, |drawer| drawer.chain_id == source);
+        match event {
+            doodle_game::DoodleEvent::PlayerJoined { .. }
+            | doodle_game::DoodleEvent::PlayerLeft { .. }
+            | doodle_game::DoodleEvent::PlayerKicked { .. }
+            | doodle_game::DoodleEvent::SettingsChanged { .. }
+            | doodle_game::DoodleEvent::GameStarted { .. }
+            | doodle_game::DoodleEvent::DrawerChosen { .. }
+            | doodle_game::DoodleEvent::AutoPickRequested { .. }
+            | doodle_game::DoodleEvent::TurnSkipped { .. }
+            | doodle_game::DoodleEvent::RoundEnded { .. }
+            | doodle_game::DoodleEvent::GameEnded { .. }
+            | doodle_game::DoodleEvent::MatchEnded { .. } => is_host,
+            doodle_game::DoodleEvent::WordChosen { .. }
+            | doodle_game::DoodleEvent::ChatMessage { .. }
+            | doodle_game::DoodleEvent::HintRevealed { .. } => is_host || is_drawer,
+            // Only the drawer who committed may reveal
+            doodle_game::DoodleEvent::WordRevealed { commitment, .. } => {
+                is_host || room.turn_audits.iter()
+                    .chain(room.current_turn.iter())
+                    .any(|turn| &turn.commitment == commitment && turn.drawer_chain_id == source)
+            }
+            doodle_game::DoodleEvent::TurnComplete { .. } => is_drawer,
+        }
+    }
+
+    /// Count an event dropped by `is_trusted_source`, per event and source chain
+    fn record_rejected_event(&mut self, source: ChainId, event: &doodle_game::DoodleEvent) {
+        let timestamp = self.runtime.system_time();
+        let mut rejected = self.state.rejected_events.get().clone();
+        match rejected.iter_mut().find(|entry| entry.event == event.name() && entry.source_chain_id == source) {
+            Some(entry) => {
+                entry.count += 1;
+                entry.last_rejected_at = timestamp;
+            }
+            None => rejected.push(RejectedEvents {
+                event: event.name().to_string(),
+                source_chain_id: source,
+                count: 1,
+                last_rejected_at: timestamp,
+            }),
+        }
+        self.state.rejected_events.set(rejected);
+    }
+
+    /// Drawer events the host re-emits on its own stream for the players
+    fn is_relayed(event: &doodle_game::DoodleEvent) -> bool {
+        matches!(
+            event,
+            doodle_game::DoodleEvent::WordChosen { .. }
+                | doodle_game::DoodleEvent::ChatMessage { .. }
+                | doodle_game::DoodleEvent::WordRevealed { .. }
+                | doodle_game::DoodleEvent::HintRevealed { .. }
+        )
+    }
+
+    /// Reject room-control messages that did not come from our host (close-guess feedback may also come from the drawer)
+    fn check_sender(&self, message: &CrossChainMessage, sender: ChainId) -> Result<(), DoodleError> {
+        match message {
+            CrossChainMessage::InitialStateSync { room_data } => room_data.ensure_host(sender),
+            CrossChainMessage::RoomDeleted { .. } | CrossChainMessage::AutoPickWord { .. } | CrossChainMessage::Kicked { .. } => {
+                self.room()?.ensure_host(sender)
+            }
+            CrossChainMessage::CloseGuess { .. } => {
+                let room = self.room()?;
+                room.ensure_host(sender).or_else(|_| room.ensure_drawer(sender))
+            }
+            CrossChainMessage::HostGuessCheck { .. } => self.room()?.ensure_drawer(sender),
+            _ => Ok(()),
+        }
+    }
+
+    /// Reject messages about a room this chain is no longer in
+    fn check_room(&self, message: &CrossChainMessage) -> Result<(), DoodleError> {
+        match message.target_room() {
+            Some(room_id) if self.room()?.room_id != room_id => Err(DoodleError::WrongRoom(room_id.to_string())),
+            _ => Ok(()),
+        }
+    }
+
+    fn room(&self) -> Result<GameRoom, DoodleError> {
+        self.state.room.get().clone().ok_or(DoodleError::NoActiveRoom)
+    }
+
+    /// Apply an event to our room through the shared reducer, then emit it so replicas apply the same event
+    fn publish(&mut self, room: &mut GameRoom, event: doodle_game::DoodleEvent) -> Result<(), DoodleError> {
+        room.apply(&event)?;
+        self.runtime.emit(room.stream_name(), &event);
+        Ok(())
+    }
+
+    /// Host only: end the current turn and pick the next drawer, advancing the round or ending the game when everyone has drawn
+    /// Shared by ChooseDrawer and the deadline checks; `reason` is carried in the emitted event
+    fn advance_turn(&mut self, reason: TurnEndReason) -> Result<(), DoodleError> {
+        let mut room = self.room()?;
+        let timestamp = self.runtime.system_time();
+        
+        // If the host was drawing, its turn ends here: reveal the word before moving on
+        self.reveal_pending_word(&mut room);
+        self.state.room.set(Some(room.clone()));
+        
+        // Note: Logic for saving previous blob hash moved to async workflow.
+        // Frontend publishes blob, collects hash, and sends all hashes on LeaveRoom.
+
+        // Check if all players have drawn in current round before choosing next drawer
+        if room.has_all_players_drawn_in_round() {
+            // Collected as of the turn's end so the last drawer's points are included
+            let scores = room.closing_scores();
+            let previous_round = room.current_round;
+            
+            if room.current_round >= room.total_rounds {
+                // Game ended - no more rounds
+                
+                // Important: We do NOT archive here automatically anymore if we rely on explicit LeaveRoom with hashes.
+                // However, if the game ends naturally, we might want to trigger archiving.
+                // But since we want to collect LAST ROUND'S blob hash from frontend, 
+                // we should probably defer archiving to the explicit EndMatch/LeaveRoom call from host?
+                // OR, we can archive what we have so far?
+                // The user said: "host must pass empty string or null on choose drawer... and [blob hashes] on LeaveRoom"
+                
+                let event = doodle_game::DoodleEvent::GameEnded {
+                    room_id: room.room_id.clone(),
+                    final_scores: scores,
+                    reason,
+                    timestamp,
+                };
+                self.publish(&mut room, event)?;
+                self.state.room.set(Some(room));
+                eprintln!("[CHOOSE_DRAWER] Game ended after round {} at {} ({:?})", previous_round, timestamp, reason);
+                return Ok(());
+            }
+            
+            // All players have drawn - advance to next round
+            let event = doodle_game::DoodleEvent::RoundEnded {
+                room_id: room.room_id.clone(),
+                scores,
+                reason,
+                timestamp,
+            };
+            self.publish(&mut room, event)?;
+            eprintln!("[CHOOSE_DRAWER] Round {} completed, advancing to round {}", previous_round, room.current_round);
+        }
+        
+        // Choose next drawer (either in same round or new round)
+        let drawer_index = room.next_drawer_index().ok_or(DoodleError::NoActivePlayers)?;
+        let drawer_name = room.players[drawer_index].name.clone();
+        
+        // Emit event to all subscribers - NO previous blob hash here
+        let event = doodle_game::DoodleEvent::DrawerChosen {
+            room_id: room.room_id.clone(),
+            drawer_index,
+            drawer_name: drawer_name.clone(),
+            timestamp,
+            previous_blob_hash: None, // Logic moved to async publish
+            reason,
+        };
+        self.publish(&mut room, event)?;
+        self.state.room.set(Some(room.clone()));
+        self.prepare_local_turn(&room);
+        
+        eprintln!("[CHOOSE_DRAWER] Drawer chosen: {} (index: {}) at {} ({:?})", drawer_name, drawer_index, timestamp, reason);
+        Ok(())
+    }
+
+    /// Host only: advance the turn if the drawing or word-selection deadline has passed
+    /// Runs on Tick and before every incoming message, so a stalled client cannot freeze the game
+    fn check_deadlines(&mut self) {
+        let current_chain = self.runtime.chain_id();
+        let now = self.runtime.system_time();
+        let expired = self.state.room.get().as_ref()
+            .filter(|room| room.host_chain_id == current_chain)
+            .and_then(|room| room.expired_deadline(now));
+        
+        if let Some(reason) = expired {
+            if reason == TurnEndReason::WordSelectionTimeout && !self.handle_word_timeout() {
+                return;
+            }
+            eprintln!("[DEADLINE] Turn deadline passed ({:?}), advancing", reason);
+            if let Err(error) = self.advance_turn(reason) {
+                eprintln!("[DEADLINE] ERROR: {}", error);
+            }
+        }
+    }
+
+    /// Host only: remove a player (optionally banning them), stop listening to their chain and tell it to leave
+    /// If the kicked player was drawing, the turn passes to the next drawer
+    fn kick_player(&mut self, player_chain_id: ChainId, banned: bool) -> Result<(), DoodleError> {
+        let mut room = self.room()?;
+        let was_member = room.ensure_kickable(player_chain_id).is_ok();
+        let drawer_kicked = room.is_in_progress()
+            && room.get_current_drawer().map_or(false, |drawer| drawer.chain_id == player_chain_id);
+        
+        let event = doodle_game::DoodleEvent::PlayerKicked {
+            room_id: room.room_id.clone(),
+            player_chain_id,
+            banned,
+            timestamp: self.runtime.system_time(),
+        };
+        self.publish(&mut room, event)?;
+        self.state.room.set(Some(room.clone()));
+        
+        if was_member {
+            let app_id = self.runtime.application_id().forget_abi();
+            self.runtime.unsubscribe_from_events(player_chain_id, app_id, room.stream_name());
+            self.runtime.send_message(player_chain_id, CrossChainMessage::Kicked {
+                room_id: room.room_id.clone(),
+                banned,
+            });
+        }
+        eprintln!("[KICK_PLAYER] Removed chain {} from room {} (banned: {})", player_chain_id, room.room_id, banned);
+        
+        if drawer_kicked {
+            eprintln!("[KICK_PLAYER] Kicked player was drawing; choosing the next one");
+            if let Err(error) = self.advance_turn(TurnEndReason::DrawerLeft) {
+                eprintln!("[KICK_PLAYER] ERROR: {}", error);
+            }
+        }
+        Ok(())
+    }
+
+    /// Host only: admit the sender of a JoinRequest, or queue it if the room requires approval
+    fn handle_join_request(
+        &mut self,
+        player_chain_id: ChainId,
+        room_id: Option<String>,
+        player_name: String,
+        avatar_json: String,
+        password: Option<String>,
+    ) -> Result<(), DoodleError> {
+        let room = self.room()?;
+        room.ensure_host(self.runtime.chain_id())?;
+        // Invitations name the room they were sent for
+        if let Some(room_id) = room_id.filter(|room_id| room_id != &room.room_id) {
+            return Err(DoodleError::WrongRoom(room_id));
+        }
+        
+        // Ban list, capacity, privacy and password (invited chains skip the last two)
+        let invited = self.state.sent_invitations.get().contains(&player_chain_id.to_string());
+        room.check_join(player_chain_id, password.as_deref(), invited)?;
+        
+        // Strangers wait for the host in approval-required rooms
+        if room.settings.approval_required && !invited && !room.is_returning(player_chain_id) {
+            let mut queue = self.state.join_queue.get().clone();
+            queue.retain(|application| application.chain_id != player_chain_id);
+            queue.push(JoinApplication {
+                chain_id: player_chain_id,
+                player_name,
+                avatar_json,
+                requested_at: self.runtime.system_time(),
+            });
+            self.state.join_queue.set(queue);
+            eprintln!("[JOIN_REQUEST] Queued chain {:?} for host approval", player_chain_id);
+            return Ok(());
+        }
+        
+        self.admit_player(room, player_chain_id, player_name, avatar_json)
+    }
+
+    /// Host only: add a player to the room and send it the room state
+    fn admit_player(&mut self, mut room: GameRoom, player_chain_id: ChainId, player_name: String, avatar_json: String) -> Result<(), DoodleError> {
+        // Display names are unique per room; clashes get a numeric suffix
+        let player_name = room.unique_name(player_chain_id, &player_name);
+        let player = Player {
+            chain_id: player_chain_id,
+            name: player_name.clone(),
+            avatar_json,
+            score: 0,
+            has_guessed: false,
+            status: PlayerStatus::Active,
+        };
+        
+        // Emit PlayerJoined event for EXISTING players (new player gets state via InitialStateSync)
+        // This notifies other players that someone joined
+        let event = doodle_game::DoodleEvent::PlayerJoined {
+            room_id: room.room_id.clone(),
+            player,
+            timestamp: self.runtime.system_time(),
+        };
+        self.publish(&mut room, event)?;
+        self.state.room.set(Some(room.clone()));
+        
+        // HOST: Subscribe to this player's events immediately
+        // This way we're subscribed to ALL players and just filter by current_drawer
+        self.subscribe_to_player(player_chain_id);
+        
+        // Send initial state to the new player (includes all players)
+        self.runtime.send_message(player_chain_id, CrossChainMessage::InitialStateSync {
+            room_data: room,
+        });
+        eprintln!("[JOIN_REQUEST] Player '{}' added to room, subscribed, and initial state sent", player_name);
+        Ok(())
+    }
+
+    /// Open a new room hosted by this chain
+    fn create_room(&mut self, host_name: String, avatar_json: String, settings: RoomSettings, password: Option<String>) -> Result<GameRoom, DoodleError> {
+        self.check_settings(&settings)?;
+        let current_chain = self.runtime.chain_id();
+        let timestamp = self.runtime.system_time();
+        let room_number = *self.state.rooms_created.get() + 1;
+        self.state.rooms_created.set(room_number);
+        
+        // The hash is salted with the room ID, so it can only be computed once the room exists
+        let settings = RoomSettings { password_hash: None, ..settings };
+        let mut room = doodle_game::GameRoom::new(current_chain, room_number, host_name, avatar_json, settings, timestamp);
+        room.settings.password_hash = room.updated_password_hash(password.as_deref());
+        self.state.room.set(Some(room.clone()));
+        self.state.pending_join.set(None);
+        self.state.join_queue.set(Vec::new());
+        self.leave_match_queue();
+        
+        // HOST: Subscribe to self (host is also a player)
+        self.subscribe_to_player(current_chain);
+        Ok(room)
+    }
+
+    /// Player side: ask a host to join its room and remember that we are waiting for the answer
+    fn request_join(
+        &mut self,
+        host_chain_id: ChainId,
+        room_id: Option<String>,
+        player_name: String,
+        avatar_json: String,
+        password: Option<String>,
+    ) {
+        self.leave_match_queue();
+        self.state.pending_join.set(Some(PendingJoin {
+            host_chain_id,
+            room_id: room_id.clone(),
+            requested_at: self.runtime.system_time(),
+            rejection: None,
+        }));
+        self.runtime.send_message(host_chain_id, CrossChainMessage::JoinRequest {
+            room_id,
+            player_name,
+            avatar_json,
+            password,
+        });
+    }
+
+    /// Host only: take a request off the approval queue
+    fn take_join_application(&mut self, player_chain_id: ChainId) -> Result<JoinApplication, DoodleError> {
+        let mut queue = self.state.join_queue.get().clone();
+        let position = queue.iter().position(|application| application.chain_id == player_chain_id)
+            .ok_or_else(|| DoodleError::NoJoinRequest(player_chain_id.to_string()))?;
+        let application = queue.remove(position);
+        self.state.join_queue.set(queue);
+        Ok(application)
+    }
+
+    /// Host only: turn away every queued join request that matches, so nobody waits forever
+    fn reject_join_applications(&mut self, reason: JoinRejectReason, matches: impl Fn(&JoinApplication) -> bool) {
+        let (rejected, kept): (Vec<_>, Vec<_>) = self.state.join_queue.get().iter().cloned().partition(matches);
+        if rejected.is_empty() {
+            return;
+        }
+        for application in &rejected {
+            self.runtime.send_message(application.chain_id, CrossChainMessage::JoinRejected { reason });
+        }
+        self.state.join_queue.set(kept);
+        eprintln!("[JOIN_QUEUE] Turned away {} join requests ({:?})", rejected.len(), reason);
+    }
+
+    /// Host only: turn away queued join requests the host didn't answer in time
+    fn expire_join_applications(&mut self) {
+        let now = self.runtime.system_time();
+        self.reject_join_applications(JoinRejectReason::Expired, |application| application.is_expired(now));
+    }
+
+    /// Whether we are waiting on a join request to this host
+    fn is_joining(&self, host_chain_id: ChainId) -> bool {
+        self.state.pending_join.get().as_ref()
+            .map_or(false, |pending| pending.host_chain_id == host_chain_id && pending.rejection.is_none())
+    }
+
+    /// Drawer only: commit to one of the offered words and start the drawing phase
+    fn commit_word(&mut self, word: String, salt: String, outcome: TurnOutcome) -> Result<(), DoodleError> {
+        // Only words offered to this drawer can be chosen (use the bank's spelling)
+        let word = doodle_game::offered_word(self.state.word_candidates.get(), &word)?;
+        
+        let mut room = self.room()?;
+        let timestamp = self.runtime.system_time();
+        
+        // Anything derived from chain data would let other players brute-force the word
+        if salt.is_empty() {
+            return Err(DoodleError::MissingSalt);
+        }
+        let commitment = doodle_game::word_commitment(&word, &salt);
+        
+        // Host-validated rooms: only the host gets a hash it can check guesses against
+        let guess_check = match room.settings.guess_validation {
+            GuessValidation::Host => Some(GuessCheck::new(&word, doodle_game::guess_check_salt(&salt), commitment.clone())),
+            GuessValidation::Drawer => None,
+        };
+        
+        // Emit event with timestamp and commitment only (not the word)
+        let event = doodle_game::DoodleEvent::WordChosen {
+            room_id: room.room_id.clone(),
+            commitment,
+            timestamp,
+            outcome,
+        };
+        self.publish(&mut room, event)?;
+        if let Some(check) = guess_check {
+            if room.host_chain_id == self.runtime.chain_id() {
+                self.state.guess_check.set(Some(check));
+            } else {
+                self.runtime.send_message(room.host_chain_id, CrossChainMessage::HostGuessCheck {
+                    room_id: room.room_id.clone(),
+                    check,
+                });
+            }
+        }
+        
+        // Store the word and salt only on drawer's chain (secret until the turn ends)
+        self.state.current_word.set(Some(word));
+        self.state.word_salt.set(Some(salt));
+        self.state.correct_guesses.set(Vec::new());
+        self.state.word_candidates.set(Vec::new());
+        
+        // First hint: word shape only
+        self.publish_due_hint(&mut room);
+        self.state.room.set(Some(room));
+        
+        eprintln!("[CHOOSE_WORD] Word chosen at timestamp {} ({:?})", timestamp, outcome);
+        Ok(())
+    }
+
+    /// Scoring chain only: end the turn once every active guesser has found the word
+    /// The host advances directly; a drawer's chain tells the host with a TurnComplete event
+    fn finish_turn_if_all_guessed(&mut self) {
+        let Some(room) = self.state.room.get().clone() else {
+            return;
+        };
+        if room.game_state != GameState::Drawing || !room.settings.ends_turn_early() || !room.all_guessers_done() {
+            return;
+        }
+        
+        if room.host_chain_id == self.runtime.chain_id() {
+            eprintln!("[TURN_COMPLETE] Everyone guessed, advancing");
+            if let Err(error) = self.advance_turn(TurnEndReason::AllGuessed) {
+                eprintln!("[TURN_COMPLETE] ERROR: {}", error);
+            }
+        } else {
+            let mut room = room;
+            let event = doodle_game::DoodleEvent::TurnComplete {
+                room_id: room.room_id.clone(),
+                round: room.current_round,
+                timestamp: self.runtime.system_time(),
+            };
+            match self.publish(&mut room, event) {
+                Ok(()) => eprintln!("[TURN_COMPLETE] Everyone guessed, told the host"),
+                Err(error) => eprintln!("[TURN_COMPLETE] ERROR: {}", error),
+            }
+        }
+    }
+
+    /// Drawer only: pick the first offered word when the selection window expired
+    /// Uses the salt the drawer's client reserved with ReserveWordSalt; without one the turn is skipped
+    fn auto_pick_word(&mut self) {
+        let Some(word) = self.state.word_candidates.get().first().cloned() else {
+            eprintln!("[AUTO_PICK] No candidates to pick from; the host will skip this turn");
+            return;
+        };
+        let salt = self.state.word_salt.get().clone().unwrap_or_default();
+        if let Err(error) = self.commit_word(word, salt, TurnOutcome::AutoPicked) {
+            eprintln!("[AUTO_PICK] ERROR: {}", error);
+        }
+    }
+
+    /// Host only: handle an expired word-selection window per the room's WordTimeoutAction
+    /// Returns true if the turn should advance now
+    fn handle_word_timeout(&mut self) -> bool {
+        let Some(mut room) = self.state.room.get().clone() else {
+            return false;
+        };
+        let current_chain = self.runtime.chain_id();
+        let drawer_chain_id = room.get_current_drawer().map(|drawer| drawer.chain_id);
+        
+        // First expiry in AutoPick rooms: have the drawer's chain pick instead of skipping
+        if room.settings.word_timeout_action == WordTimeoutAction::AutoPick && room.auto_pick_requested_at.is_none() {
+            if drawer_chain_id == Some(current_chain) {
+                self.auto_pick_word();
+                if self.state.room.get().as_ref().map_or(false, |room| room.game_state == GameState::Drawing) {
+                    return false;
+                }
+            } else if let Some(drawer_chain) = drawer_chain_id {
+                // Replicas restart the selection window too, so every chain agrees on the deadline
+                let timestamp = self.runtime.system_time();
+                let event = doodle_game::DoodleEvent::AutoPickRequested {
+                    room_id: room.room_id.clone(),
+                    drawer_index: room.current_drawer_index.unwrap_or_default(),
+                    timestamp,
+                };
+                let requested = self.publish(&mut room, event);
+                if let Err(error) = requested {
+                    eprintln!("[WORD_TIMEOUT] ERROR: {}", error);
+                    return false;
+                }
+                self.runtime.send_message(drawer_chain, CrossChainMessage::AutoPickWord {
+                    room_id: room.room_id.clone(),
+                    round: room.current_round,
+                });
+                self.state.room.set(Some(room));
+                eprintln!("[WORD_TIMEOUT] Asked drawer chain {:?} to auto-pick a word", drawer_chain);
+                return false;
+            }
+        }
+        
+        // Skip: record it in the turn history before the next drawer is chosen
+        let timestamp = self.runtime.system_time();
+        let drawer_index = room.current_drawer_index.unwrap_or_default();
+        let drawer_name = room.get_current_drawer().map(|drawer| drawer.name.clone()).unwrap_or_default();
+        let event = doodle_game::DoodleEvent::TurnSkipped {
+            room_id: room.room_id.clone(),
+            drawer_index,
+            drawer_name: drawer_name.clone(),
+            timestamp,
+        };
+        let skipped = self.publish(&mut room, event);
+        if let Err(error) = skipped {
+            eprintln!("[WORD_TIMEOUT] ERROR: {}", error);
+            return false;
+        }
+        self.state.room.set(Some(room));
+        eprintln!("[WORD_TIMEOUT] Skipped {} for not choosing a word", drawer_name);
+        true
+    }
+
+    /// Reveal the word committed for the turn that just ended (drawer's chain only)
+    /// Applies the reveal to the local room copy and emits it so the host can relay it
+    fn reveal_pending_word(&mut self, room: &mut GameRoom) {
+        let word = self.state.current_word.get().clone();
+        let salt = self.state.word_salt.get().clone();
+        if let (Some(word), Some(salt)) = (word, salt) {
+            let commitment = doodle_game::word_commitment(&word, &salt);
+            let timestamp = self.runtime.system_time();
+            let correct_guesses = self.state.correct_guesses.get().clone();
+            let event = doodle_game::DoodleEvent::WordRevealed {
+                room_id: room.room_id.clone(),
+                commitment: commitment.clone(),
+                word,
+                salt,
+                correct_guesses,
+                timestamp,
+            };
+            let revealed = self.publish(room, event);
+
+            self.state.current_word.set(None);
+            self.state.word_salt.set(None);
+            self.state.correct_guesses.set(Vec::new());
+            match revealed {
+                Ok(()) => {
+                    let status = room.turn_audits.iter().rev().find(|turn| turn.commitment == commitment).map(|turn| turn.status);
+                    eprintln!("[REVEAL_WORD] Word revealed at turn end, local audit: {:?}", status);
+                }
+                Err(error) => eprintln!("[REVEAL_WORD] ERROR: {}", error),
+            }
+        }
+    }
+
+    /// Host only: bring the registry's listing of our room up to date (register, heartbeat or unregister)
+    fn sync_room_listing(&mut self) {
+        let Some(registry_chain_id) = self.runtime.application_parameters().registry_chain_id else {
+            return;
+        };
+        let current_chain = self.runtime.chain_id();
+        let now = self.runtime.system_time();
+        let next = self.state.room.get().as_ref()
+            .filter(|room| room.host_chain_id == current_chain)
+            .and_then(|room| RoomListing::of(room, now));
+        let messages = registry::updates(self.state.listed_room.get().as_ref(), next.as_ref());
+        if messages.is_empty() {
+            return;
+        }
+        for message in messages {
+            self.runtime.send_message(registry_chain_id, message);
+        }
+        self.state.listed_room.set(next);
+    }
+
+    /// Registry only: store a host's listing, dropping stale ones on the way
+    fn list_public_room(&mut self, sender: ChainId, mut listing: RoomListing) -> Result<(), DoodleError> {
+        if !self.is_registry() {
+            return Err(DoodleError::NotRegistry);
+        }
+        if listing.host_chain_id != sender {
+            return Err(DoodleError::NotHost);
+        }
+        let now = self.runtime.system_time();
+        // Staleness is judged by the registry's clock, not the host's
+        listing.updated_at = now;
+        let mut listings = self.state.public_rooms.get().clone();
+        listings.retain(|listed| !listed.is_stale(now) && listed.host_chain_id != sender);
+        listings.push(listing);
+        self.state.public_rooms.set(listings);
+        Ok(())
+    }
+
+    /// Registry only: remove a host's listing
+    fn unlist_public_room(&mut self, sender: ChainId, room_id: &str) {
+        let now = self.runtime.system_time();
+        let mut listings = self.state.public_rooms.get().clone();
+        listings.retain(|listed| !listed.is_stale(now) && !(listed.host_chain_id == sender && listed.room_id == room_id));
+        self.state.public_rooms.set(listings);
+    }
+
+    /// Whether this chain keeps the room directory and the matchmaking queue
+    fn is_registry(&mut self) -> bool {
+        self.runtime.application_parameters().registry_chain_id == Some(self.runtime.chain_id())
+    }
+
+    /// Player side: stop waiting for a quick-play match (no-op when not queued)
+    fn leave_match_queue(&mut self) {
+        if self.state.match_ticket.get().is_none() {
+            return;
+        }
+        self.state.match_ticket.set(None);
+        if let Some(registry_chain_id) = self.runtime.application_parameters().registry_chain_id {
+            self.runtime.send_message(registry_chain_id, CrossChainMessage::LeaveMatchQueue);
+        }
+    }
+
+    /// Matchmaker: send a queued player to an open public room, or queue it and start a new room
+    /// once enough players are waiting (the oldest of them hosts)
+    fn enqueue_for_match(&mut self, ticket: MatchTicket) -> Result<(), DoodleError> {
+        if !self.is_registry() {
+            return Err(DoodleError::NotRegistry);
+        }
+        let now = self.runtime.system_time();
+        let mut queue = self.state.match_queue.get().clone();
+        queue.retain(|queued| queued.chain_id != ticket.chain_id);
+        let (expired, mut queue): (Vec<_>, Vec<_>) = queue.into_iter().partition(|queued| queued.is_expired(now));
+        for queued in &expired {
+            self.runtime.send_message(queued.chain_id, CrossChainMessage::MatchCancelled);
+        }
+        
+        let mut listings = self.state.public_rooms.get().clone();
+        if let Some(listing) = registry::open_room(&listings, &ticket.preferences, now).cloned() {
+            self.runtime.send_message(ticket.chain_id, CrossChainMessage::JoinMatch {
+                host_chain_id: listing.host_chain_id,
+                room_id: listing.room_id.clone(),
+            });
+            // Count the seat as taken until the host's next heartbeat
+            if let Some(listed) = listings.iter_mut().find(|listed| listed.room_id == listing.room_id) {
+                listed.player_count += 1;
+            }
+            self.state.public_rooms.set(listings);
+            self.state.match_queue.set(queue);
+            eprintln!("[MATCHMAKER] Sent chain {:?} to room {}", ticket.chain_id, listing.room_id);
+            return Ok(());
+        }
+        
+        queue.push(ticket.clone());
+        if let Some(group) = registry::take_match(&mut queue, &ticket) {
+            let (settings, rounds) = registry::match_room(&group);
+            let host_chain_id = group[0].chain_id;
+            let players = group[1..].iter().map(|queued| queued.chain_id).collect();
+            self.runtime.send_message(host_chain_id, CrossChainMessage::HostMatch { settings, rounds, players });
+            eprintln!("[MATCHMAKER] Matched {} players, chain {:?} hosts", group.len(), host_chain_id);
+        }
+        self.state.match_queue.set(queue);
+        Ok(())
+    }
+
+    /// Chosen match host: open the room and send the other players to it
+    /// If this chain is no longer queued (or already in a room) the match is called off
+    fn host_match(&mut self, settings: RoomSettings, rounds: u32, players: Vec<ChainId>) -> Result<(), DoodleError> {
+        if self.state.room.get().is_some() {
+            return Err(DoodleError::AlreadyInRoom);
+        }
+        let ticket = self.state.match_ticket.get().clone().ok_or(DoodleError::NotQueued)?;
+        self.state.match_ticket.set(None);
+        
+        let mut room = self.create_room(ticket.player_name, ticket.avatar_json, settings, None)?;
+        // Suggested rounds for StartGame; GameStarted sets the real value
+        room.total_rounds = rounds;
+        self.state.room.set(Some(room.clone()));
+        for player_chain_id in players {
+            self.runtime.send_message(player_chain_id, CrossChainMessage::JoinMatch {
+                host_chain_id: room.host_chain_id,
+                room_id: room.room_id.clone(),
+            });
+        }
+        eprintln!("[HOST_MATCH] Room {} created for a quick-play match", room.room_id);
+        Ok(())
+    }
+
+    /// Apply a message that passed the room and sender checks
+    fn handle_message(&mut self, message: CrossChainMessage, sender: ChainId) {
+        match message {
+            doodle_game::CrossChainMessage::Tick { .. } => {
+                // Deadlines were already checked above
+            }
+
+            doodle_game::CrossChainMessage::AutoPickWord { round, .. } => {
+                let current_chain = self.runtime.chain_id();
+                let should_pick = self.state.room.get().as_ref().map_or(false, |room| {
+                    room.current_round == round
+                        && room.game_state == GameState::WaitingForWord
+                        && room.get_current_drawer().map_or(false, |drawer| drawer.chain_id == current_chain)
+                });
+                if should_pick {
+                    eprintln!("[AUTO_PICK] Host requested an automatic word pick for round {}", round);
+                    self.auto_pick_word();
+                }
+            }
+
+            doodle_game::CrossChainMessage::JoinRequest { room_id, player_name, avatar_json, password } => {
+                let player_chain_id = sender;
+                eprintln!("[JOIN_REQUEST] Received join request from player '{}' on chain {:?}", player_name, player_chain_id);
+                
+                // Never leave the joining chain waiting: every refusal is answered
+                if let Err(error) = self.handle_join_request(player_chain_id, room_id, player_name, avatar_json, password) {
+                    eprintln!("[JOIN_REQUEST] Refused chain {:?}: {}", player_chain_id, error);
+                    self.runtime.send_message(player_chain_id, CrossChainMessage::JoinRejected {
+                        reason: JoinRejectReason::from_error(&error),
+                    });
+                }
+            }
+
+            doodle_game::CrossChainMessage::InitialStateSync { room_data } => {
+                // Only rooms we asked to join
+                if !self.is_joining(sender) {
+                    eprintln!("[INITIAL_STATE_SYNC] No pending join request to {:?} - ignoring", sender);
+                    return;
+                }
+                self.state.pending_join.set(None);
+                eprintln!("[INITIAL_STATE_SYNC] Received initial room state from host");
+                eprintln!("[INITIAL_STATE_SYNC] Room: {} players, state: {:?}, round: {}/{}", 
+                    room_data.players.len(), room_data.game_state, room_data.current_round, room_data.total_rounds);
+                
+                // Subscribe to single aggregated host stream (instead of 8 separate streams)
+                let host_chain_id = room_data.host_chain_id.to_string();
+                let already_subscribed = self.state.subscribed_to_host.get()
+                    .as_ref()
+                    .map(|h| h == &host_chain_id)
+                    .unwrap_or(false);
+                
+                if !already_subscribed {
+                    let app_id = self.runtime.application_id().forget_abi();
+                    
+                    // Single subscription to aggregated game_events stream
+                    // Host re-emits ALL events to this stream
+                    eprintln!("[INITIAL_STATE_SYNC] Subscribing to aggregated stream of room {}", room_data.room_id);
+                    self.runtime.subscribe_to_events(room_data.host_chain_id, app_id, room_data.stream_name());
+                    
+                    self.state.subscribed_to_host.set(Some(host_chain_id));
+                    eprintln!("[INITIAL_STATE_SYNC] Subscribed to host game_events stream (1 subscription total)");
+                }
+                
+                // Set the complete room state on player's chain
+                self.state.room.set(Some(room_data));
+                
+                eprintln!("[INITIAL_STATE_SYNC] Player now has complete room state");
+            }
+
+            doodle_game::CrossChainMessage::JoinRejected { reason } => {
+                if !self.is_joining(sender) {
+                    eprintln!("[JOIN_REJECTED] No pending join request to {:?} - ignoring", sender);
+                    return;
+                }
+                eprintln!("[JOIN_REJECTED] Host {:?} refused to let us join: {:?}", sender, reason);
+                let mut pending = self.state.pending_join.get().clone();
+                if let Some(pending) = pending.as_mut() {
+                    pending.rejection = Some(reason);
+                }
+                self.state.pending_join.set(pending);
+            }
+
+            doodle_game::CrossChainMessage::GuessSubmission { guess, round, .. } => {
+                let guesser_chain_id = sender;
+                eprintln!("[GUESS_SUBMISSION] Received guess '{}' from chain {:?}", guess, guesser_chain_id);
+                
+                if let Some(mut room) = self.state.room.get().clone() {
+                    if room.expired_deadline(self.runtime.system_time()) == Some(TurnEndReason::DrawTimeout) {
+                        eprintln!("[GUESS_SUBMISSION] Drawing time is over - ignoring");
+                        return;
+                    }
+                    
+                    if room.current_round == round {
+                        // Score against the plaintext word (drawer's chain) or the drawer's hash of it (host chain)
+                        let is_host = room.host_chain_id == self.runtime.chain_id();
+                        let verdict = match room.settings.guess_validation {
+                            GuessValidation::Drawer => self.state.current_word.get().as_ref()
+                                .map(|word| guess::check_guess(word, &guess)),
+                            // Only exact (normalized) matches can be detected against a hash
+                            GuessValidation::Host if is_host => self.state.guess_check.get().as_ref()
+                                .filter(|check| room.current_turn.as_ref().map_or(false, |turn| turn.commitment == check.commitment))
+                                .map(|check| if check.matches(&guess) { GuessVerdict::Correct } else { GuessVerdict::Wrong }),
+                            GuessValidation::Host => None,
+                        };
+                        
+                        let Some(verdict) = verdict else {
+                            eprintln!("[GUESS_SUBMISSION] No word to check against on this chain - ignoring");
+                            return;
+                        };
+                        let is_correct = verdict == GuessVerdict::Correct;
+                        
+                        // Only active players other than the drawer can guess
+                        if let Err(error) = room.ensure_guesser(guesser_chain_id) {
+                            eprintln!("[GUESS_SUBMISSION] Rejected guess from {}: {}", guesser_chain_id, error);
+                            return;
+                        }
+                        
+                        // Знаходимо ім'я гравця за чейн ід
+                        let guesser_name = room.players.iter()
+                            .find(|p| p.chain_id == guesser_chain_id)
+                            .map(|p| p.name.clone())
+                            .unwrap_or_else(|| format!("Player_{}", guesser_chain_id));
+                        
+                        // Check if player already guessed
+                        let already_guessed = room.players.iter()
+                            .any(|p| p.chain_id == guesser_chain_id && p.has_guessed);
+                        
+                        if already_guessed {
+                            eprintln!("[GUESS_SUBMISSION] Player '{}' already guessed - ignoring", guesser_name);
+                            return;
+                        }
+                        
+                        // Calculate points from guess order and time since the word was chosen
+                        let elapsed_ms = room.elapsed_millis(self.runtime.system_time());
+                        let points = if is_correct {
+                            room.guess_points(elapsed_ms)
+                        } else {
+                            0
+                        };
+                        
+                        // Drawer-scored correct guesses are committed now and opened with the reveal
+                        let guess_commitment = match (is_correct, self.state.word_salt.get()) {
+                            (true, Some(salt)) if room.settings.guess_validation == GuessValidation::Drawer => {
+                                Some(doodle_game::word_commitment(&guess, salt))
+                            }
+                            _ => None,
+                        };
+                        
+                        let chat_message = ChatMessage {
+                            player_chain_id: guesser_chain_id,
+                            player_name: guesser_name.clone(),
+                            message: if is_correct { 
+                                format!("[Correct! +{} points]", points)
+                            } else { 
+                                guess.clone() 
+                            },
+                            is_correct_guess: is_correct,
+                            points_awarded: points,
+                            is_close: false, // Broadcast copy never reveals closeness
+                            elapsed_ms,
+                            sequence: room.next_chat_sequence(),
+                            guess_commitment: guess_commitment.clone(),
+                        };
+                        
+                        // Update state on the scoring chain and emit - drawer's event is re-emitted by host, host's goes straight to players
+                        let event = doodle_game::DoodleEvent::ChatMessage {
+                            room_id: room.room_id.clone(),
+                            message: chat_message,
+                        };
+                        let scored = self.publish(&mut room, event);
+                        if let Err(error) = scored {
+                            eprintln!("[GUESS_SUBMISSION] ERROR: {}", error);
+                            return;
+                        }
+                        if is_correct {
+                            if guess_commitment.is_some() {
+                                let mut correct_guesses = self.state.correct_guesses.get().clone();
+                                correct_guesses.push(guess.clone());
+                                self.state.correct_guesses.set(correct_guesses);
+                            }
+                            eprintln!("[GUESS_SUBMISSION] Player '{}' guessed correctly! Awarded {} points", guesser_name, points);
+                        }
+                        
+                        // Incoming guesses are a chance to catch up on the hint schedule (no-op off the drawer's chain)
+                        self.publish_due_hint(&mut room);
+                        
+                        self.state.room.set(Some(room));
+                        
+                        eprintln!("[GUESS_SUBMISSION] ChatMessage emitted for guess '{}' from '{}'", guess, guesser_name);
+                        
+                        // Tell only the guesser that they are close
+                        if verdict == GuessVerdict::Close {
+                            self.runtime.send_message(guesser_chain_id, doodle_game::CrossChainMessage::CloseGuess {
+                                room_id: room.room_id.clone(),
+                                round,
+                                guess: guess.clone(),
+                            });
+                            eprintln!("[GUESS_SUBMISSION] Close-guess feedback sent to '{}'", guesser_name);
+                        }
+                        
+                        if is_correct {
+                            self.finish_turn_if_all_guessed();
+                        }
+                    }
+                }
+            }
+
+            doodle_game::CrossChainMessage::HostGuessCheck { check, .. } => {
+                if self.room().map_or(false, |room| room.host_chain_id == self.runtime.chain_id()) {
+                    self.state.guess_check.set(Some(check));
+                    eprintln!("[GUESS_CHECK] Received the drawer's guess check for this turn");
+                }
+            }
+
+            doodle_game::CrossChainMessage::CloseGuess { round, guess, .. } => {
+                if let Some(mut room) = self.state.room.get().clone() {
+                    if room.current_round == round {
+                        let mut close_guesses = self.state.close_guesses.get().clone();
+                        close_guesses.push(guess);
+                        self.state.close_guesses.set(close_guesses);
+                        
+                        self.mark_own_close_guesses(&mut room);
+                        self.state.room.set(Some(room));
+                        eprintln!("[CLOSE_GUESS] Guess marked as close on this chain");
+                    }
+                }
+            }
+
+            doodle_game::CrossChainMessage::RoomDeleted { timestamp, archived_room, .. } => {
+                eprintln!("[ROOM_DELETED] Received room deletion message at {}", timestamp);
+                
+                // Save archived room if provided
+                if let Some(archived) = archived_room {
+                    let mut archived_list = self.state.archived_rooms.get().clone();
+                    if !archived_list.iter().any(|r| r.room_id == archived.room_id) {
+                        eprintln!("[ROOM_DELETED] Saving archived room history locally: {}", archived.room_id);
+                        archived_list.push(archived);
+                        self.state.archived_rooms.set(archived_list);
+                    }
+                }
+                
+                // Get room data before clearing (for unsubscribe)
+                let room_data = self.state.room.get().clone();
+                
+                // Unsubscribe from host events (cleanup)
+                if let Some(room) = &room_data {
+                    let app_id = self.runtime.application_id().forget_abi();
+                    
+                    eprintln!("[ROOM_DELETED] Unsubscribing from host chain {:?}", room.host_chain_id);
+                    self.runtime.unsubscribe_from_events(room.host_chain_id, app_id, room.stream_name());
+                    eprintln!("[ROOM_DELETED] Unsubscribed from game_events stream");
+                }
+                
+                // Completely clear player state
+                self.state.room.set(None);
+                self.state.current_word.set(None);
+                self.state.word_salt.set(None);
+                self.state.correct_guesses.set(Vec::new());
+                self.state.word_candidates.set(Vec::new());
+                self.state.subscribed_to_host.set(None); // Clear subscription tracking
+                
+                if room_data.is_some() {
+                    eprintln!("[ROOM_DELETED] Player disconnected from room, unsubscribed, and all state cleared");
+                } else {
+                    eprintln!("[ROOM_DELETED] Player was already disconnected");
+                }
+            }
+
+            doodle_game::CrossChainMessage::PlayerLeft { player_name, timestamp, .. } => {
+                let player_chain_id = sender;
+                eprintln!("[PLAYER_LEFT] Player {:?} ('{:?}') left at {}", player_chain_id, player_name, timestamp);
+                if let Some(mut room) = self.state.room.get().clone() {
                     let app_id = self.runtime.application_id().forget_abi();
-                    let stream = StreamName::from(format!("game_events_{}", room.room_id));
+                    self.runtime.unsubscribe_from_events(player_chain_id, app_id, room.stream_name());
+                    let drawer_left = room.is_in_progress()
+                        && room.get_current_drawer().map_or(false, |drawer| drawer.chain_id == player_chain_id);
+                    let event = doodle_game::DoodleEvent::PlayerLeft {
+                        room_id: room.room_id.clone(),
+                        player_chain_id,
+                        timestamp,
+                    };
+                    let left = self.publish(&mut room, event);
+                    if let Err(error) = left {
+                        eprintln!("[PLAYER_LEFT] ERROR: {}", error);
+                        return;
+                    }
+                    self.state.room.set(Some(room));
+                    eprintln!("[PLAYER_LEFT] Player marked as left and host unsubscribed from their events");
                     
-                    eprintln!("[SUBSCRIPTION] Host subscribing to player chain {:?}", player_chain);
-                    self.runtime.subscribe_to_events(player_chain, app_id, stream);
-                    eprintln!("[SUBSCRIPTION] Subscribed to player events");
+                    // Don't leave the room waiting on a drawer who is gone
+                    if drawer_left {
+                        eprintln!("[PLAYER_LEFT] Current drawer left; choosing the next one");
+                        if let Err(error) = self.advance_turn(TurnEndReason::DrawerLeft) {
+                            eprintln!("[PLAYER_LEFT] ERROR: {}", error);
+                        }
+                    }
+                }
+            }
+            
+            doodle_game::CrossChainMessage::Kicked { banned, .. } => {
+                eprintln!("[KICKED] Host removed this player from the room (banned: {})", banned);
+                
+                if let Some(room) = self.state.room.get().clone() {
+                    let app_id = self.runtime.application_id().forget_abi();
+                    self.runtime.unsubscribe_from_events(room.host_chain_id, app_id, room.stream_name());
+                }
+                
+                self.state.room.set(None);
+                self.state.current_word.set(None);
+                self.state.word_salt.set(None);
+                self.state.correct_guesses.set(Vec::new());
+                self.state.word_candidates.set(Vec::new());
+                self.state.close_guesses.set(Vec::new());
+                self.state.subscribed_to_host.set(None);
+                eprintln!("[KICKED] Unsubscribed from host and cleared room state");
+            }
+            
+            doodle_game::CrossChainMessage::FriendRequest => {
+                let mut received = self.state.friend_requests_received.get().clone();
+                let requester_str = sender.to_string();
+                if !received.contains(&requester_str) {
+                    received.push(requester_str);
+                    self.state.friend_requests_received.set(received);
+                }
+            }
+            
+            doodle_game::CrossChainMessage::FriendAccepted => {
+                 let target_str = sender.to_string();
+                 
+                 // Only chains we asked can accept
+                 if !self.state.friend_requests_sent.get().contains(&target_str) {
+                     eprintln!("[FRIEND_ACCEPTED] No pending request to {} - ignoring", target_str);
+                     return;
+                 }
+                 
+                 // Add to friends
+                 let mut friends = self.state.friends.get().clone();
+                 if !friends.contains(&target_str) {
+                     friends.push(target_str.clone());
+                     self.state.friends.set(friends);
+                 }
+                 
+                 // Remove from sent requests
+                 let mut sent = self.state.friend_requests_sent.get().clone();
+                 if let Some(pos) = sent.iter().position(|x| x == &target_str) {
+                     sent.remove(pos);
+                     self.state.friend_requests_sent.set(sent);
+                 }
+            }
+            
+            doodle_game::CrossChainMessage::RoomInvitation { room_id, timestamp } => {
+                let mut invitations = self.state.room_invitations.get().clone();
+                
+                // Avoid duplicates from same host
+                if !invitations.iter().any(|inv| inv.host_chain_id == sender && inv.room_id == room_id) {
+                    invitations.push(Invitation {
+                        host_chain_id: sender,
+                        timestamp,
+                        room_id,
+                    });
+                    self.state.room_invitations.set(invitations);
+                }
+            }
+            
+            doodle_game::CrossChainMessage::RoomInvitationCancelled { room_id } => {
+                let mut invitations = self.state.room_invitations.get().clone();
+                
+                if let Some(pos) = invitations.iter().position(|inv| inv.host_chain_id == sender && inv.room_id == room_id) {
+                    invitations.remove(pos);
+                    self.state.room_invitations.set(invitations);
+                }
+            }
+
+            doodle_game::CrossChainMessage::RegisterRoom { listing } | doodle_game::CrossChainMessage::RoomHeartbeat { listing } => {
+                let room_id = listing.room_id.clone();
+                match self.list_public_room(sender, listing) {
+                    Ok(()) => eprintln!("[REGISTRY] Listed room {} of chain {:?}", room_id, sender),
+                    Err(error) => eprintln!("[REGISTRY] Refused listing of room {}: {}", room_id, error),
+                }
+            }
+
+            doodle_game::CrossChainMessage::UnregisterRoom { room_id } => {
+                self.unlist_public_room(sender, &room_id);
+                eprintln!("[REGISTRY] Unlisted room {} of chain {:?}", room_id, sender);
+            }
+
+            doodle_game::CrossChainMessage::QueueForMatch { player_name, avatar_json, preferences } => {
+                let ticket = MatchTicket {
+                    chain_id: sender,
+                    player_name,
+                    avatar_json,
+                    preferences,
+                    queued_at: self.runtime.system_time(),
+                };
+                if let Err(error) = self.enqueue_for_match(ticket) {
+                    eprintln!("[MATCHMAKER] Refused to queue chain {:?}: {}", sender, error);
+                    self.runtime.send_message(sender, CrossChainMessage::MatchCancelled);
+                }
+            }
+
+            doodle_game::CrossChainMessage::LeaveMatchQueue => {
+                let mut queue = self.state.match_queue.get().clone();
+                queue.retain(|queued| queued.chain_id != sender);
+                self.state.match_queue.set(queue);
+            }
+
+            doodle_game::CrossChainMessage::HostMatch { settings, rounds, players } => {
+                // Only the matchmaker picks hosts
+                if self.runtime.application_parameters().registry_chain_id != Some(sender) {
+                    eprintln!("[HOST_MATCH] Ignoring match from chain {:?}, not the matchmaker", sender);
+                    return;
+                }
+                if let Err(error) = self.host_match(settings, rounds, players.clone()) {
+                    eprintln!("[HOST_MATCH] Cannot host the match: {}", error);
+                    for player_chain_id in players {
+                        self.runtime.send_message(player_chain_id, CrossChainMessage::MatchCancelled);
+                    }
+                }
+            }
+
+            doodle_game::CrossChainMessage::JoinMatch { host_chain_id, room_id } => {
+                // From the matchmaker, or from the host it picked; only while we are queued
+                let from_matchmaker = self.runtime.application_parameters().registry_chain_id == Some(sender);
+                let Some(ticket) = self.state.match_ticket.get().clone() else {
+                    eprintln!("[JOIN_MATCH] Not queued for a match - ignoring");
+                    return;
+                };
+                if (!from_matchmaker && sender != host_chain_id) || self.state.room.get().is_some() {
+                    eprintln!("[JOIN_MATCH] Ignoring match instructions from chain {:?}", sender);
+                    return;
+                }
+                self.state.match_ticket.set(None);
+                self.request_join(host_chain_id, Some(room_id), ticket.player_name, ticket.avatar_json, None);
+                eprintln!("[JOIN_MATCH] Join request sent to matched host {:?}", host_chain_id);
+            }
+
+            doodle_game::CrossChainMessage::MatchCancelled => {
+                if self.state.match_ticket.get().is_some() {
+                    self.state.match_ticket.set(None);
+                    eprintln!("[MATCH_CANCELLED] No match found, queue again");
                 }
             }
         }
@@ -50,7 +1288,7 @@ impl DoodleGameContract {
 impl Contract for DoodleGameContract {
     type Message = doodle_game::CrossChainMessage;
     type InstantiationArgument = ();
-    type Parameters = ();
+    type Parameters = DoodleParameters;
     type EventValue = doodle_game::DoodleEvent;
 
     async fn load(runtime: ContractRuntime<Self>) -> Self {
@@ -63,6 +1301,9 @@ impl Contract for DoodleGameContract {
     async fn instantiate(&mut self, _argument: ()) {
         self.state.room.set(None);
         self.state.current_word.set(None);
+        self.state.word_salt.set(None);
+        self.state.correct_guesses.set(Vec::new());
+        self.state.word_candidates.set(Vec::new());
         self.state.friends.set(Vec::new());
         self.state.friend_requests_received.set(Vec::new());
         self.state.friend_requests_sent.set(Vec::new());
@@ -71,281 +1312,199 @@ impl Contract for DoodleGameContract {
         eprintln!("[INIT] Doodle Game contract initialized on chain {:?}", self.runtime.chain_id());
     }
 
-    async fn execute_operation(&mut self, operation: Operation) -> () {
+    async fn execute_operation(&mut self, operation: Operation) -> Result<(), DoodleError> {
+        let current_chain = self.runtime.chain_id();
+        operation.precheck(self.state.room.get().as_ref(), current_chain, self.state.word_candidates.get())?;
+        
         match operation {
-            Operation::CreateRoom { host_name, avatar_json } => {
-                let host_chain_id = self.runtime.chain_id().to_string();
-                let timestamp = self.runtime.system_time().micros().to_string();
-                
-                let room = doodle_game::GameRoom::new(host_chain_id.clone(), host_name.clone(), avatar_json, timestamp);
-                self.state.room.set(Some(room.clone()));
-                
-                // HOST: Subscribe to self (host is also a player)
-                self.subscribe_to_player(&host_chain_id);
-                
-                eprintln!("[CREATE_ROOM] Room created by host '{}'", host_name);
+            Operation::CreateRoom { host_name, avatar_json, settings, password } => {
+                let room = self.create_room(host_name.clone(), avatar_json, settings, password)?;
+                eprintln!("[CREATE_ROOM] Room {} created by host '{}'", room.room_id, host_name);
             }
 
-            Operation::JoinRoom { host_chain_id, player_name, avatar_json } => {
+            Operation::JoinRoom { host_chain_id, player_name, avatar_json, password } => {
                 eprintln!("[JOIN_ROOM] Sending join request to host chain '{}' from player '{}'", host_chain_id, player_name);
+                let target_chain = doodle_game::parse_chain_id(&host_chain_id)?;
                 
-                if let Ok(target_chain) = host_chain_id.parse() {
-                    // Send join request directly - host will send InitialStateSync which will trigger subscription
-                    let message = doodle_game::CrossChainMessage::JoinRequest {
-                        player_chain_id: self.runtime.chain_id(),
-                        player_name,
-                        avatar_json,
-                    };
-                    
-                    self.runtime.send_message(target_chain, message);
-                    eprintln!("[JOIN_ROOM] Join request sent to chain {}", host_chain_id);
-                } else {
-                    eprintln!("[JOIN_ROOM] Invalid host_chain_id format: {}", host_chain_id);
-                }
+                // Send join request directly - host answers with InitialStateSync (which triggers subscription) or JoinRejected
+                self.request_join(target_chain, None, player_name, avatar_json, password);
+                eprintln!("[JOIN_ROOM] Join request sent to chain {}", host_chain_id);
             }
 
-            Operation::StartGame { rounds, seconds_per_round } => {
-                if let Some(mut room) = self.state.room.get().clone() {
-                    let timestamp = self.runtime.system_time().micros().to_string();
-                    
-                    // Clean up invites
-                    let sent_invites = self.state.sent_invitations.get().clone();
-                    for target in sent_invites {
-                        if let Ok(target_chain) = target.parse::<ChainId>() {
-                             let message = doodle_game::CrossChainMessage::RoomInvitationCancelled {
-                                 host_chain_id: self.runtime.chain_id(),
-                             };
-                             self.runtime.send_message(target_chain, message);
-                        }
-                    }
-                    self.state.sent_invitations.set(Vec::new());
+            Operation::UpdateSettings { settings, password } => {
+                // Host only, in the lobby (checked by precheck)
+                self.check_settings(&settings)?;
+                let mut room = self.room()?;
+                let settings = RoomSettings { password_hash: room.updated_password_hash(password.as_deref()), ..settings };
+                let event = doodle_game::DoodleEvent::SettingsChanged {
+                    room_id: room.room_id.clone(),
+                    settings,
+                    timestamp: self.runtime.system_time(),
+                };
+                self.publish(&mut room, event)?;
+                self.state.room.set(Some(room));
+                eprintln!("[UPDATE_SETTINGS] Room settings changed");
+            }
 
-                    room.start_game(rounds, seconds_per_round, timestamp.clone());
-                    
-                    // Automatically choose first drawer for round 1
-                    if let Some(drawer_index) = room.choose_drawer(timestamp.clone()) {
-                        // SAFE: Check bounds before accessing
-                        if let Some(drawer) = room.players.get(drawer_index) {
-                            let drawer_name = drawer.name.clone();
-                            
-                            self.state.room.set(Some(room.clone()));
-                            
-                            // Emit single combined event with game start + drawer info
-                            self.runtime.emit(format!("game_events_{}", room.room_id).into(), &doodle_game::DoodleEvent::GameStarted { 
-                                rounds, 
-                                seconds_per_round,
-                                drawer_index,
-                                drawer_name: drawer_name.clone(),
-                                timestamp: timestamp.clone()
-                            });
-                            
-                            eprintln!("[START_GAME] Game started with {} rounds, {} seconds per round, first drawer: {}", rounds, seconds_per_round, drawer_name);
-                        } else {
-                            eprintln!("[START_GAME] ERROR: Drawer index {} out of bounds (players: {})", drawer_index, room.players.len());
-                        }
+            Operation::StartGame { rounds, seconds_per_round } => {
+                // Host only (checked by precheck); uses the settings chosen in the lobby
+                let mut room = self.room()?;
+                let timestamp = self.runtime.system_time();
+                
+                // Automatically choose first drawer for round 1
+                let drawer_index = room.players.iter()
+                    .position(|p| p.status == PlayerStatus::Active)
+                    .ok_or(DoodleError::NoActivePlayers)?;
+                let drawer_name = room.players[drawer_index].name.clone();
+                
+                // Emit single combined event with game start + drawer info
+                let event = doodle_game::DoodleEvent::GameStarted {
+                    room_id: room.room_id.clone(),
+                    rounds,
+                    seconds_per_round,
+                    settings: room.settings.clone(),
+                    drawer_index,
+                    drawer_name: drawer_name.clone(),
+                    timestamp,
+                };
+                self.publish(&mut room, event)?;
+                
+                // Clean up invites
+                let sent_invites = self.state.sent_invitations.get().clone();
+                for target in sent_invites {
+                    if let Ok(target_chain) = target.parse::<ChainId>() {
+                         self.runtime.send_message(target_chain, doodle_game::CrossChainMessage::RoomInvitationCancelled {
+                             room_id: room.room_id.clone(),
+                         });
                     }
                 }
+                self.state.sent_invitations.set(Vec::new());
+                
+                self.state.room.set(Some(room.clone()));
+                self.prepare_local_turn(&room);
+                
+                eprintln!("[START_GAME] Game started with {} rounds, {} seconds per round, first drawer: {}", rounds, seconds_per_round, drawer_name);
             }
 
             Operation::ChooseDrawer => {
-                if let Some(mut room) = self.state.room.get().clone() {
-                    let timestamp = self.runtime.system_time().micros().to_string();
-                    
-                    // Host only check
-                    if self.runtime.chain_id() != room.host_chain_id.parse().unwrap() {
-                        panic!("Only host can select drawer");
-                    }
-                    
-                    // Note: Logic for saving previous blob hash moved to async workflow.
-                    // Frontend publishes blob, collects hash, and sends all hashes on LeaveRoom.
+                // Host only (checked by precheck)
+                self.advance_turn(TurnEndReason::HostAdvanced)?;
+            }
 
-                    // Check if all players have drawn in current round before choosing next drawer
-                    let should_advance_round = room.has_all_players_drawn_in_round();
-                    
-                    if should_advance_round {
-                        // All players have drawn - advance to next round
-                        let scores: Vec<(String, u32)> = room.players.iter()
-                            .map(|p| (p.name.clone(), p.score))
-                            .collect();
-                        
-                        let previous_round = room.current_round;
-                        room.advance_to_next_round(timestamp.clone());
-                        
-                        if room.game_state == GameState::GameEnded {
-                            // Game ended - no more rounds
-                            
-                            // Important: We do NOT archive here automatically anymore if we rely on explicit LeaveRoom with hashes.
-                            // However, if the game ends naturally, we might want to trigger archiving.
-                            // But since we want to collect LAST ROUND'S blob hash from frontend, 
-                            // we should probably defer archiving to the explicit EndMatch/LeaveRoom call from host?
-                            // OR, we can archive what we have so far?
-                            // The user said: "host must pass empty string or null on choose drawer... and [blob hashes] on LeaveRoom"
-                            
-                            self.state.room.set(Some(room.clone()));
-                            
-                            // Emit game ended event
-                            self.runtime.emit(format!("game_events_{}", room.room_id).into(), &doodle_game::DoodleEvent::GameEnded { 
-                                final_scores: scores,
-                                timestamp: timestamp.clone()
-                            });
-                            eprintln!("[CHOOSE_DRAWER] Game ended after round {} at {}", previous_round, timestamp);
-                            return;
-                        } else {
-                            // Emit round ended event
-                            self.runtime.emit(format!("game_events_{}", room.room_id).into(), &doodle_game::DoodleEvent::RoundEnded { 
-                                scores,
-                                timestamp: timestamp.clone()
-                            });
-                            eprintln!("[CHOOSE_DRAWER] Round {} completed, advancing to round {}", previous_round, room.current_round);
-                        }
-                    }
-                    
-                    // Choose next drawer (either in same round or new round)
-                    if let Some(drawer_index) = room.choose_drawer(timestamp.clone()) {
-                        // SAFE: Check bounds before accessing
-                        if let Some(drawer) = room.players.get(drawer_index) {
-                            let drawer_name = drawer.name.clone();
-                            
-                            self.state.room.set(Some(room.clone()));
-                            
-                            // Emit event to all subscribers - NO previous blob hash here
-                            self.runtime.emit(format!("game_events_{}", room.room_id).into(), &doodle_game::DoodleEvent::DrawerChosen { 
-                                drawer_index, 
-                                drawer_name: drawer_name.clone(),
-                                timestamp: timestamp.clone(),
-                                previous_blob_hash: None, // Logic moved to async publish
-                            });
-                            
-                            eprintln!("[CHOOSE_DRAWER] Drawer chosen: {} (index: {}) at {}", drawer_name, drawer_index, timestamp);
-                        } else {
-                            eprintln!("[CHOOSE_DRAWER] ERROR: Drawer index {} out of bounds (players: {})", drawer_index, room.players.len());
-                        }
-                    } else {
-                        eprintln!("[CHOOSE_DRAWER] ERROR: Failed to choose drawer (no players?)");
-                    }
+            Operation::Tick => {
+                // Anyone may tick: the host checks deadlines itself, other chains nudge the host
+                let room = self.room()?;
+                if room.host_chain_id == current_chain {
+                    self.check_deadlines();
+                    self.expire_join_applications();
+                } else {
+                    self.runtime.send_message(room.host_chain_id, CrossChainMessage::Tick { room_id: room.room_id });
                 }
             }
 
-            Operation::ChooseWord { word } => {
-                if let Some(mut room) = self.state.room.get().clone() {
-                    let timestamp = self.runtime.system_time().micros().to_string();
-                    room.choose_word(timestamp.clone());
-                    self.state.room.set(Some(room));
-                    
-                    // Store the word only on drawer's chain (secret)
-                    self.state.current_word.set(Some(word));
-                    
-                    // Emit event with timestamp only (not the word)
-                    if let Some(room) = self.state.room.get() {
-                        self.runtime.emit(format!("game_events_{}", room.room_id).into(), &doodle_game::DoodleEvent::WordChosen { 
-                            timestamp: timestamp.clone() 
-                        });
-                    }
-                    
-                    eprintln!("[CHOOSE_WORD] Word chosen at timestamp {}", timestamp);
-                }
+            Operation::ChooseWord { word, salt } => {
+                // Current drawer only (checked by precheck)
+                self.commit_word(word, salt, TurnOutcome::Played)?;
             }
 
+            Operation::ReserveWordSalt { salt } => {
+                // Current drawer only (checked by precheck)
+                self.state.word_salt.set(Some(salt));
+                eprintln!("[RESERVE_SALT] Salt reserved for an auto-picked word");
+            }
 
+            Operation::PublishHint => {
+                // Current drawer only (checked by precheck)
+                let mut room = self.room()?;
+                self.publish_due_hint(&mut room);
+                self.state.room.set(Some(room));
+            }
 
             Operation::GuessWord { guess } => {
-                if let Some(room) = self.state.room.get() {
-                    if let Some(drawer) = room.get_current_drawer() {
-                        // Send guess to drawer's chain
-                        if let Ok(drawer_chain) = drawer.chain_id.parse() {
-                            let message = doodle_game::CrossChainMessage::GuessSubmission {
-                                guesser_chain_id: self.runtime.chain_id(),
-                                guess,
-                                round: room.current_round,
-                            };
-                            
-                            self.runtime.send_message(drawer_chain, message);
-                            eprintln!("[GUESS_WORD] Guess sent to drawer's chain");
-                        }
-                    }
-                }
+                let room = self.room()?;
+                // Send guess to whichever chain scores guesses in this room
+                let validator_chain = match room.settings.guess_validation {
+                    GuessValidation::Drawer => room.get_current_drawer().map(|drawer| drawer.chain_id).ok_or(DoodleError::NoActivePlayers)?,
+                    GuessValidation::Host => room.host_chain_id,
+                };
+                
+                let message = doodle_game::CrossChainMessage::GuessSubmission {
+                    room_id: room.room_id.clone(),
+                    guess,
+                    round: room.current_round,
+                };
+                
+                self.runtime.send_message(validator_chain, message);
+                eprintln!("[GUESS_WORD] Guess sent to {:?} chain", room.settings.guess_validation);
             }
 
-
-
             Operation::EndMatch => {
-                if let Some(room) = self.state.room.get().clone() {
-                    let timestamp = self.runtime.system_time().micros().to_string();
-                    
-                    // Only host can end the match
-                    let current_chain = self.runtime.chain_id();
-                    if room.host_chain_id == current_chain.to_string() {
-                        let room_host = room.host_chain_id.clone();
-                        let player_count = room.players.len();
-                        
-                        eprintln!("[END_MATCH] Starting room deletion process. Host: {}, Players: {}", room_host, player_count);
-                        
-                        let mut archived_list = self.state.archived_rooms.get().clone();
-                        let archive_number = archived_list.len() + 1;
-                        let archived = ArchivedRoom {
-                            room_id: format!("{}#{}", room.room_id, archive_number),
-                            blob_hashes: room.blob_hashes.clone(),
-                            timestamp: timestamp.clone(),
-                        };
-                        archived_list.push(archived.clone());
-                        self.state.archived_rooms.set(archived_list);
-
-                        // 1. FIRST: Send room deletion message to ALL players (with archive data)
-                        eprintln!("[END_MATCH] Sending RoomDeleted with Archive to {} players", room.players.len());
-                        for player in &room.players {
-                            eprintln!("[END_MATCH] Preparing deletion message for player '{}' on chain '{}'", player.name, player.chain_id);
-                            if let Ok(chain_id) = player.chain_id.parse() {
-                                let deletion_message = doodle_game::CrossChainMessage::RoomDeleted {
-                                    timestamp: timestamp.clone(),
-                                    archived_room: Some(archived.clone()),
-                                };
-                                self.runtime.send_message(chain_id, deletion_message);
-                                eprintln!("[END_MATCH] ✅ RoomDeleted message sent to chain {:?} ({})", chain_id, player.name);
-                            } else {
-                                eprintln!("[END_MATCH] ❌ ERROR: Failed to parse chain_id '{}' for player '{}'", player.chain_id, player.name);
-                            }
-                        }
-                        
-                        eprintln!("[END_MATCH] Waiting for players to process deletion messages...");
-                        
-                        // 2. THEN: Emit match ended event for any local subscribers
-                        self.runtime.emit(format!("game_events_{}", room.room_id).into(), &doodle_game::DoodleEvent::MatchEnded { 
-                            timestamp: timestamp.clone()
-                        });
-                        
-                        // 3. THEN: Unsubscribe HOST from ALL players (host cleanup)
-                        let app_id = self.runtime.application_id().forget_abi();
-                        let stream = StreamName::from(format!("game_events_{}", room.room_id));
-                        for player in &room.players {
-                            if let Ok(player_chain) = player.chain_id.parse() {
-                                eprintln!("[END_MATCH] Host unsubscribing from player chain {:?}", player_chain);
-                                self.runtime.unsubscribe_from_events(player_chain, app_id, stream.clone());
-                            }
-                        }
-                        
+                // Only host can end the match (checked by precheck)
+                let room = self.room()?;
+                let timestamp = self.runtime.system_time();
+                
+                let room_host = room.host_chain_id;
+                let player_count = room.players.len();
+                
+                eprintln!("[END_MATCH] Starting room deletion process. Host: {}, Players: {}", room_host, player_count);
+                
+                let mut archived_list = self.state.archived_rooms.get().clone();
+                let archived = ArchivedRoom {
+                    room_id: room.room_id.clone(),
+                    blob_hashes: room.blob_hashes.clone(),
+                    timestamp,
+                };
+                archived_list.push(archived.clone());
+                self.state.archived_rooms.set(archived_list);
 
+                // 1. FIRST: Send room deletion message to ALL players (with archive data)
+                eprintln!("[END_MATCH] Sending RoomDeleted with Archive to {} players", room.players.len());
+                for player in &room.players {
+                    eprintln!("[END_MATCH] Preparing deletion message for player '{}' on chain '{}'", player.name, player.chain_id);
+                    let deletion_message = doodle_game::CrossChainMessage::RoomDeleted {
+                        room_id: room.room_id.clone(),
+                        timestamp,
+                        archived_room: Some(archived.clone()),
+                    };
+                    self.runtime.send_message(player.chain_id, deletion_message);
+                    eprintln!("[END_MATCH] ✅ RoomDeleted message sent to chain {:?} ({})", player.chain_id, player.name);
+                }
+                
+                eprintln!("[END_MATCH] Waiting for players to process deletion messages...");
+                
+                // 2. THEN: Emit match ended event for any local subscribers
+                self.runtime.emit(room.stream_name(), &doodle_game::DoodleEvent::MatchEnded { 
+                    room_id: room.room_id.clone(),
+                    timestamp,
+                });
+                
+                // 3. THEN: Unsubscribe HOST from ALL players (host cleanup)
+                let app_id = self.runtime.application_id().forget_abi();
+                let stream = room.stream_name();
+                for player in &room.players {
+                    eprintln!("[END_MATCH] Host unsubscribing from player chain {:?}", player.chain_id);
+                    self.runtime.unsubscribe_from_events(player.chain_id, app_id, stream.clone());
+                }
 
+                // 4. Nobody queued for approval can join any more
+                self.reject_join_applications(JoinRejectReason::NoRoom, |_| true);
 
-                        // 5. FINALLY: Delete host's own room state (last step!)
-                        self.state.room.set(None);
-                        self.state.current_word.set(None);
-                        
-                        eprintln!("[END_MATCH] Room hosted by '{}' completely deleted at {}. {} players disconnected and unsubscribed.", 
-                                 room_host, timestamp, player_count);
-                    } else {
-                        eprintln!("[END_MATCH] ERROR: Only host can end the match. Current chain: {:?}, Host chain: {}", current_chain, room.host_chain_id);
-                    }
-                } else {
-                    eprintln!("[END_MATCH] ERROR: No active room to end");
-                }
+                // 5. FINALLY: Delete host's own room state (last step!)
+                self.state.room.set(None);
+                self.state.current_word.set(None);
+                self.state.word_salt.set(None);
+                self.state.correct_guesses.set(Vec::new());
+                self.state.word_candidates.set(Vec::new());
+                
+                eprintln!("[END_MATCH] Room hosted by '{}' completely deleted at {}. {} players disconnected and unsubscribed.", 
+                         room_host, timestamp, player_count);
             }
 
-
             Operation::LeaveRoom { blob_hashes } => {
-                let mut room = self.state.room.get().clone().expect("Room not found");
+                let room = self.room()?;
                 
                 // If Host leaves, treat as EndMatch -> Archive and Delete Room
-                if self.runtime.chain_id() == room.host_chain_id.parse().unwrap() {
+                if room.host_chain_id == current_chain {
                      // Create Archive
                      // Use provided hashes or fall back to internal ones (if any were mixed)
                      let mut final_hashes = if let Some(h) = blob_hashes { h } else { Vec::new() };
@@ -353,13 +1512,12 @@ impl Contract for DoodleGameContract {
                          final_hashes = room.blob_hashes.clone();
                      }
 
-                     let timestamp = self.runtime.system_time().micros().to_string();
+                     let timestamp = self.runtime.system_time();
                      let mut archives = self.state.archived_rooms.get().clone();
-                     let archive_number = archives.len() + 1;
                      let archived_room = ArchivedRoom {
-                         room_id: format!("{}#{}", room.room_id, archive_number),
+                         room_id: room.room_id.clone(),
                          blob_hashes: final_hashes,
-                         timestamp: timestamp.clone(), 
+                         timestamp, 
                      };
                      
                      // Store Archive Locally
@@ -367,436 +1525,231 @@ impl Contract for DoodleGameContract {
                      self.state.archived_rooms.set(archives);
 
                     let msg = CrossChainMessage::RoomDeleted { 
-                        timestamp: timestamp.clone(),
+                        room_id: room.room_id.clone(),
+                        timestamp,
                         archived_room: Some(archived_room) 
                     };
                     
                     for player in &room.players {
-                        if let Ok(chain_id) = player.chain_id.parse::<ChainId>() {
-                             if chain_id != self.runtime.chain_id() {
-                                 let _ = self.runtime.send_message(chain_id, msg.clone());
-                             }
+                        if player.chain_id != current_chain {
+                            self.runtime.send_message(player.chain_id, msg.clone());
                         }
                     }
                     
                     // Clear state
+                    self.reject_join_applications(JoinRejectReason::NoRoom, |_| true);
                     self.state.room.set(None);
                     self.state.current_word.set(None);
+                    self.state.word_salt.set(None);
+                    self.state.correct_guesses.set(Vec::new());
+                    self.state.word_candidates.set(Vec::new());
 
                 } else {
                     // Regular player leaving
-                    let current_chain = self.runtime.chain_id();
-                    let player_name = room.players.iter().find(|p| p.chain_id == current_chain.to_string()).map(|p| p.name.clone());
-
-                     // Notify host
-                    if let Ok(host_id) = room.host_chain_id.parse::<linera_sdk::linera_base_types::ChainId>() {
-                         let msg = CrossChainMessage::PlayerLeft {
-                             player_chain_id: current_chain,
-                             player_name,
-                             timestamp: self.runtime.system_time().micros().to_string(),
-                         };
-                         let _ = self.runtime.send_message(host_id, msg);
-                    }
+                    let player_name = room.players.iter().find(|p| p.chain_id == current_chain).map(|p| p.name.clone());
+
+                    // Notify host
+                    let msg = CrossChainMessage::PlayerLeft {
+                        room_id: room.room_id.clone(),
+                        player_name,
+                        timestamp: self.runtime.system_time(),
+                    };
+                    self.runtime.send_message(room.host_chain_id, msg);
 
                     let app_id = self.runtime.application_id().forget_abi();
-                    if let Ok(host_chain) = room.host_chain_id.parse() {
-                        let stream = StreamName::from(format!("game_events_{}", room.room_id));
-                        self.runtime.unsubscribe_from_events(host_chain, app_id, stream);
-                    }
+                    self.runtime.unsubscribe_from_events(room.host_chain_id, app_id, room.stream_name());
 
                     self.state.room.set(None);
                     self.state.current_word.set(None);
+                    self.state.word_salt.set(None);
+                    self.state.correct_guesses.set(Vec::new());
+                    self.state.word_candidates.set(Vec::new());
                     self.state.subscribed_to_host.set(None);
                 }
             }
 
+            Operation::ApproveJoin { player_chain_id } => {
+                // Host only (checked by precheck)
+                // Everything is checked before the queue changes, so a failed approval has no effect
+                let player_chain_id = doodle_game::parse_chain_id(&player_chain_id)?;
+                let room = self.room()?;
+                let now = self.runtime.system_time();
+                let application = self.state.join_queue.get().iter()
+                    .find(|application| application.chain_id == player_chain_id && !application.is_expired(now))
+                    .cloned()
+                    .ok_or_else(|| DoodleError::NoJoinRequest(player_chain_id.to_string()))?;
+                // Room may have filled up (or the chain been banned) while the request waited
+                if room.is_banned(player_chain_id) {
+                    return Err(DoodleError::Banned);
+                }
+                room.ensure_room_for(player_chain_id)?;
+                
+                self.admit_player(room, application.chain_id, application.player_name, application.avatar_json)?;
+                self.take_join_application(player_chain_id)?;
+                self.expire_join_applications();
+            }
+
+            Operation::RejectJoin { player_chain_id } => {
+                // Host only (checked by precheck)
+                let player_chain_id = doodle_game::parse_chain_id(&player_chain_id)?;
+                let application = self.take_join_application(player_chain_id)?;
+                self.runtime.send_message(application.chain_id, CrossChainMessage::JoinRejected {
+                    reason: JoinRejectReason::NotApproved,
+                });
+                eprintln!("[REJECT_JOIN] Turned down '{}' on chain {:?}", application.player_name, player_chain_id);
+            }
+
+            Operation::KickPlayer { player_chain_id } => {
+                // Host only (checked by precheck)
+                self.kick_player(doodle_game::parse_chain_id(&player_chain_id)?, false)?;
+            }
+
+            Operation::BanPlayer { player_chain_id } => {
+                // Host only (checked by precheck)
+                self.kick_player(doodle_game::parse_chain_id(&player_chain_id)?, true)?;
+            }
+
             Operation::ReadDataBlob { hash } => {
                 // Parse the hex string to DataBlobHash via CryptoHash
-                use linera_sdk::linera_base_types::{CryptoHash, DataBlobHash};
-                use std::str::FromStr;
-                
-                match CryptoHash::from_str(&hash) {
-                    Ok(crypto_hash) => {
-                        let blob_hash = DataBlobHash(crypto_hash);
-                        let data = self.runtime.read_data_blob(blob_hash);
-                        eprintln!("[READ_BLOB] Read {} bytes from blob {}", data.len(), hash);
-                    }
-                    Err(e) => {
-                        eprintln!("[READ_BLOB] ERROR: Invalid blob hash format '{}': {:?}", hash, e);
-                    }
-                }
+                let crypto_hash = CryptoHash::from_str(&hash).map_err(|_| DoodleError::InvalidBlobHash(hash.clone()))?;
+                let data = self.runtime.read_data_blob(DataBlobHash(crypto_hash));
+                eprintln!("[READ_BLOB] Read {} bytes from blob {}", data.len(), hash);
+            }
+
+            Operation::AddWordPack { hash } => {
+                let settings = RoomSettings {
+                    word_pack: Some(hash.clone()),
+                    ..RoomSettings::default()
+                };
+                let bank = self.load_word_bank(&settings).map_err(DoodleError::InvalidWordPack)?;
+                eprintln!("[WORD_PACK] Pack '{}' ({}) has {} words", bank.name, hash, bank.words.len());
+                self.remember_word_pack(&bank);
             }
 
             // Friend System
             Operation::RequestFriend { target_chain_id } => {
-                let target_chain: ChainId = target_chain_id.parse().expect("Invalid chain ID");
+                let target_chain = doodle_game::parse_chain_id(&target_chain_id)?;
                 let mut sent = self.state.friend_requests_sent.get().clone();
                 if !sent.contains(&target_chain_id) {
                     sent.push(target_chain_id.clone());
                     self.state.friend_requests_sent.set(sent);
                     
-                    let message = CrossChainMessage::FriendRequest {
-                        requester_chain_id: self.runtime.chain_id(),
-                    };
-                    self.runtime.send_message(target_chain, message);
+                    self.runtime.send_message(target_chain, CrossChainMessage::FriendRequest);
                 }
             }
             
             Operation::AcceptFriend { requester_chain_id } => {
+                let target_chain = doodle_game::parse_chain_id(&requester_chain_id)?;
                 let mut received = self.state.friend_requests_received.get().clone();
-                if let Some(pos) = received.iter().position(|x| x == &requester_chain_id) {
-                    received.remove(pos);
-                    self.state.friend_requests_received.set(received);
+                let pos = received.iter().position(|x| x == &requester_chain_id)
+                    .ok_or_else(|| DoodleError::NoFriendRequest(requester_chain_id.clone()))?;
+                received.remove(pos);
+                self.state.friend_requests_received.set(received);
+                
+                let mut friends = self.state.friends.get().clone();
+                if !friends.contains(&requester_chain_id) {
+                    friends.push(requester_chain_id.clone());
+                    self.state.friends.set(friends);
                     
-                    let mut friends = self.state.friends.get().clone();
-                    if !friends.contains(&requester_chain_id) {
-                        friends.push(requester_chain_id.clone());
-                        self.state.friends.set(friends);
-                        
-                        let target_chain: ChainId = requester_chain_id.parse().expect("Invalid chain ID");
-                        let message = CrossChainMessage::FriendAccepted {
-                            target_chain_id: self.runtime.chain_id(),
-                        };
-                        self.runtime.send_message(target_chain, message);
-                    }
+                    self.runtime.send_message(target_chain, CrossChainMessage::FriendAccepted);
                 }
             }
             
             Operation::DeclineFriend { requester_chain_id } => {
                 let mut received = self.state.friend_requests_received.get().clone();
-                if let Some(pos) = received.iter().position(|x| x == &requester_chain_id) {
-                    received.remove(pos);
-                    self.state.friend_requests_received.set(received);
-                }
+                let pos = received.iter().position(|x| x == &requester_chain_id)
+                    .ok_or(DoodleError::NoFriendRequest(requester_chain_id))?;
+                received.remove(pos);
+                self.state.friend_requests_received.set(received);
             }
             
             Operation::InviteFriend { friend_chain_id } => {
-                let friends = self.state.friends.get();
-                if friends.contains(&friend_chain_id) {
-                     let target_chain: ChainId = friend_chain_id.parse().expect("Invalid chain ID");
-                     
-                     let mut sent_invites = self.state.sent_invitations.get().clone();
-                     if !sent_invites.contains(&friend_chain_id) {
-                         sent_invites.push(friend_chain_id.clone());
-                         self.state.sent_invitations.set(sent_invites);
-                         
-                         let timestamp = self.runtime.system_time().micros().to_string();
-                         let message = CrossChainMessage::RoomInvitation {
-                             host_chain_id: self.runtime.chain_id(),
-                             timestamp,
-                         };
-                         self.runtime.send_message(target_chain, message);
-                     }
+                let room = self.room()?;
+                if !self.state.friends.get().contains(&friend_chain_id) {
+                    return Err(DoodleError::NotAFriend(friend_chain_id));
                 }
-            }
-            
-            Operation::AcceptInvite { host_chain_id, player_name, avatar_json } => {
-                 let mut invitations = self.state.room_invitations.get().clone();
-                 if let Some(pos) = invitations.iter().position(|inv| inv.host_chain_id == host_chain_id) {
-                     let invite = &invitations[pos];
-                     let invite_time: u64 = invite.timestamp.parse().unwrap_or(0);
-                     let current_time = self.runtime.system_time().micros();
-                     
-                     // 5 minutes = 300,000,000 microseconds
-                     if current_time > invite_time && current_time.saturating_sub(invite_time) <= 300_000_000 {
-                         // Valid invite
-                         invitations.remove(pos);
-                         self.state.room_invitations.set(invitations);
-                         
-                         // Execute Join Room Logic
-                         if let Ok(target_chain) = host_chain_id.parse::<ChainId>() {
-                             let message = CrossChainMessage::JoinRequest {
-                                 player_chain_id: self.runtime.chain_id(),
-                                 player_name,
-                                 avatar_json,
-                             };
-                             self.runtime.send_message(target_chain, message);
-                         }
-                     } else {
-                         // Expired
-                         invitations.remove(pos);
-                         self.state.room_invitations.set(invitations);
-                     }
-                 }
-            }
-            
-            Operation::DeclineInvite { host_chain_id } => {
-                 let mut invitations = self.state.room_invitations.get().clone();
-                 if let Some(pos) = invitations.iter().position(|inv| inv.host_chain_id == host_chain_id) {
-                     invitations.remove(pos);
-                     self.state.room_invitations.set(invitations);
-                 }
-            }
-        }
-    }
-
-    async fn execute_message(&mut self, message: Self::Message) {
-        match message {
-            doodle_game::CrossChainMessage::JoinRequest { player_chain_id, player_name, avatar_json } => {
-                eprintln!("[JOIN_REQUEST] Received join request from player '{}' on chain {:?}", player_name, player_chain_id);
+                let target_chain = doodle_game::parse_chain_id(&friend_chain_id)?;
                 
-                if let Some(mut room) = self.state.room.get().clone() {
-                    let timestamp = self.runtime.system_time().micros().to_string();
-                    let player = Player {
-                        chain_id: player_chain_id.to_string(),
-                        name: player_name.clone(),
-                        avatar_json,
-                        score: 0,
-                        has_guessed: false,
-                        status: PlayerStatus::Active,
-                    };
-                    
-                    room.add_player(player.clone());
-                    self.state.room.set(Some(room.clone()));
+                let mut sent_invites = self.state.sent_invitations.get().clone();
+                if !sent_invites.contains(&friend_chain_id) {
+                    sent_invites.push(friend_chain_id.clone());
+                    self.state.sent_invitations.set(sent_invites);
                     
-                    // HOST: Subscribe to this player's events immediately
-                    // This way we're subscribed to ALL players and just filter by current_drawer
-                    self.subscribe_to_player(&player.chain_id);
-                    
-                    // Send initial state to the new player (includes all players)
-                    let sync_message = doodle_game::CrossChainMessage::InitialStateSync {
-                        room_data: room.clone(),
+                    let message = CrossChainMessage::RoomInvitation {
+                        room_id: room.room_id,
+                        timestamp: self.runtime.system_time(),
                     };
-                    self.runtime.send_message(player_chain_id, sync_message);
-                    eprintln!("[JOIN_REQUEST] Initial state sent to player '{}'", player_name);
-                    
-                    // Emit PlayerJoined event for EXISTING players (new player already has state via InitialStateSync)
-                    // This notifies other players that someone joined
-                    self.runtime.emit(format!("game_events_{}", room.room_id).into(), &doodle_game::DoodleEvent::PlayerJoined { 
-                        player: player.clone(),
-                        timestamp: timestamp.clone()
-                    });
-                    
-                    eprintln!("[JOIN_REQUEST] Player '{}' added to room, subscribed, and PlayerJoined event emitted", player_name);
+                    self.runtime.send_message(target_chain, message);
                 }
             }
-
-            doodle_game::CrossChainMessage::InitialStateSync { room_data } => {
-                eprintln!("[INITIAL_STATE_SYNC] Received initial room state from host");
-                eprintln!("[INITIAL_STATE_SYNC] Room: {} players, state: {:?}, round: {}/{}", 
-                    room_data.players.len(), room_data.game_state, room_data.current_round, room_data.total_rounds);
+            
+            Operation::AcceptInvite { host_chain_id, player_name, avatar_json } => {
+                let target_chain = doodle_game::parse_chain_id(&host_chain_id)?;
+                let mut invitations = self.state.room_invitations.get().clone();
+                let pos = invitations.iter().position(|inv| inv.host_chain_id == target_chain)
+                    .ok_or_else(|| DoodleError::NoInvitation(host_chain_id.clone()))?;
+                let invite = invitations.remove(pos);
+                self.state.room_invitations.set(invitations);
                 
-                // Subscribe to single aggregated host stream (instead of 8 separate streams)
-                let host_chain_id = room_data.host_chain_id.clone();
-                let already_subscribed = self.state.subscribed_to_host.get()
-                    .as_ref()
-                    .map(|h| h == &host_chain_id)
-                    .unwrap_or(false);
+                let current_time = self.runtime.system_time();
                 
-                if !already_subscribed {
-                    if let Ok(host_chain) = host_chain_id.parse() {
-                        let app_id = self.runtime.application_id().forget_abi();
-                        
-                        // Single subscription to aggregated game_events stream
-                        // Host re-emits ALL events to this stream
-                        eprintln!("[INITIAL_STATE_SYNC] Subscribing to aggregated game_events_<room_id> stream");
-                        let stream = StreamName::from(format!("game_events_{}", room_data.room_id));
-                        self.runtime.subscribe_to_events(host_chain, app_id, stream);
-                        
-                        self.state.subscribed_to_host.set(Some(host_chain_id));
-                        eprintln!("[INITIAL_STATE_SYNC] Subscribed to host game_events stream (1 subscription total)");
-                    }
+                // 5 minutes (expired invites are dropped either way)
+                if current_time <= invite.timestamp || current_time.delta_since(invite.timestamp) > TimeDelta::from_secs(300) {
+                    return Err(DoodleError::InvitationExpired(host_chain_id));
                 }
                 
-                // Set the complete room state on player's chain
-                self.state.room.set(Some(room_data));
-                
-                eprintln!("[INITIAL_STATE_SYNC] Player now has complete room state");
+                // Execute Join Room Logic for the room we were invited to
+                self.request_join(target_chain, Some(invite.room_id), player_name, avatar_json, None);
             }
-
-            doodle_game::CrossChainMessage::GuessSubmission { guesser_chain_id, guess, round } => {
-                eprintln!("[GUESS_SUBMISSION] Received guess '{}' from chain {:?}", guess, guesser_chain_id);
-                
-                if let (Some(mut room), Some(current_word)) = (self.state.room.get().clone(), self.state.current_word.get()) {
-                    if room.current_round == round {
-                        // Знаходимо ім'я гравця за чейн ід
-                        let guesser_name = room.players.iter()
-                            .find(|p| p.chain_id == guesser_chain_id.to_string())
-                            .map(|p| p.name.clone())
-                            .unwrap_or_else(|| format!("Player_{}", guesser_chain_id));
-                        
-                        // Check if player already guessed
-                        let already_guessed = room.players.iter()
-                            .any(|p| p.name == guesser_name && p.has_guessed);
-                        
-                        if already_guessed {
-                            eprintln!("[GUESS_SUBMISSION] Player '{}' already guessed - ignoring", guesser_name);
-                            return;
-                        }
-                        
-                        let is_correct = current_word.to_lowercase() == guess.to_lowercase();
-                        
-                        // Calculate points based on how many players already guessed
-                        let points = if is_correct {
-                            let guessed_count = room.players.iter().filter(|p| p.has_guessed).count() as u32;
-                            // First guesser: 100, second: 90, third: 80, etc.
-                            100u32.saturating_sub(guessed_count * 10).max(10)
-                        } else {
-                            0
-                        };
-                        
-                        let chat_message = ChatMessage {
-                            player_name: guesser_name.clone(),
-                            message: if is_correct { 
-                                format!("[Correct! +{} points]", points)
-                            } else { 
-                                guess.clone() 
-                            },
-                            is_correct_guess: is_correct,
-                            points_awarded: points,
-                        };
-                        
-                        // Add to chat and update state on drawer's chain
-                        room.add_chat_message(chat_message.clone());
-                        
-                        // MEMORY OPTIMIZATION: Keep only last 10 chat messages to prevent overflow
-                        if room.chat_messages.len() > 10 {
-                            room.chat_messages = room.chat_messages.split_off(room.chat_messages.len() - 10);
-                            eprintln!("[GUESS_SUBMISSION] Trimmed chat to last 10 messages");
-                        }
-                        
-                        if is_correct {
-                            room.award_points(&guesser_name, points);
-                            eprintln!("[GUESS_SUBMISSION] Player '{}' guessed correctly! Awarded {} points", guesser_name, points);
-                        }
-                        
-                        self.state.room.set(Some(room.clone()));
-                        
-                        // Emit ChatMessage event - host will receive it and re-emit to all players
-                        if let Some(room) = self.state.room.get() {
-                            self.runtime.emit(format!("game_events_{}", room.room_id).into(), &doodle_game::DoodleEvent::ChatMessage {
-                                message: chat_message.clone()
-                            });
-                        }
-                        
-                        eprintln!("[GUESS_SUBMISSION] ChatMessage emitted for guess '{}' from '{}'", guess, guesser_name);
-                    }
-                }
+            
+            Operation::DeclineInvite { host_chain_id } => {
+                let mut invitations = self.state.room_invitations.get().clone();
+                let pos = invitations.iter().position(|inv| inv.host_chain_id.to_string() == host_chain_id)
+                    .ok_or(DoodleError::NoInvitation(host_chain_id))?;
+                invitations.remove(pos);
+                self.state.room_invitations.set(invitations);
             }
 
-            doodle_game::CrossChainMessage::RoomDeleted { timestamp, archived_room } => {
-                eprintln!("[ROOM_DELETED] Received room deletion message at {}", timestamp);
-                
-                // Save archived room if provided
-                if let Some(archived) = archived_room {
-                    let mut archived_list = self.state.archived_rooms.get().clone();
-                    if !archived_list.iter().any(|r| r.room_id == archived.room_id) {
-                        eprintln!("[ROOM_DELETED] Saving archived room history locally: {}", archived.room_id);
-                        archived_list.push(archived);
-                        self.state.archived_rooms.set(archived_list);
-                    }
-                }
-                
-                // Get room data before clearing (for unsubscribe)
-                let room_data = self.state.room.get().clone();
-                
-                // Unsubscribe from host events (cleanup)
-                if let Some(room) = &room_data {
-                    if let Ok(host_chain) = room.host_chain_id.parse() {
-                        let app_id = self.runtime.application_id().forget_abi();
-                        
-                        eprintln!("[ROOM_DELETED] Unsubscribing from host chain {:?}", host_chain);
-                        let stream = StreamName::from(format!("game_events_{}", room.room_id));
-                        self.runtime.unsubscribe_from_events(host_chain, app_id, stream);
-                        eprintln!("[ROOM_DELETED] Unsubscribed from game_events stream");
-                    }
-                }
-                
-                // Completely clear player state
-                self.state.room.set(None);
-                self.state.current_word.set(None);
-                self.state.subscribed_to_host.set(None); // Clear subscription tracking
-                
-                if room_data.is_some() {
-                    eprintln!("[ROOM_DELETED] Player disconnected from room, unsubscribed, and all state cleared");
-                } else {
-                    eprintln!("[ROOM_DELETED] Player was already disconnected");
-                }
+            Operation::QueueForMatch { player_name, avatar_json, preferences } => {
+                // Not in a room (checked by precheck)
+                let registry_chain_id = self.runtime.application_parameters().registry_chain_id.ok_or(DoodleError::NoRegistry)?;
+                self.state.match_ticket.set(Some(MatchTicket {
+                    chain_id: current_chain,
+                    player_name: player_name.clone(),
+                    avatar_json: avatar_json.clone(),
+                    preferences: preferences.clone(),
+                    queued_at: self.runtime.system_time(),
+                }));
+                self.runtime.send_message(registry_chain_id, CrossChainMessage::QueueForMatch { player_name, avatar_json, preferences });
+                eprintln!("[QUEUE_FOR_MATCH] Queued with matchmaker {:?}", registry_chain_id);
             }
 
-            doodle_game::CrossChainMessage::PlayerLeft { player_chain_id, player_name, timestamp } => {
-                eprintln!("[PLAYER_LEFT] Player {:?} ('{:?}') left at {}", player_chain_id, player_name, timestamp);
-                if let Some(mut room) = self.state.room.get().clone() {
-                    let app_id = self.runtime.application_id().forget_abi();
-                    let stream = StreamName::from(format!("game_events_{}", room.room_id));
-                    self.runtime.unsubscribe_from_events(player_chain_id, app_id, stream);
-                    let player_index = room.players.iter().position(|p| p.chain_id == player_chain_id.to_string());
-                    if let Some(idx) = player_index {
-                        if let Some(player) = room.players.get_mut(idx) {
-                            player.status = PlayerStatus::Left;
-                            player.has_guessed = false;
-                        }
-
-                        if room.current_drawer_index == Some(idx) {
-                            room.current_drawer_index = None;
-                            room.game_state = GameState::ChoosingDrawer;
-                            room.word_chosen_at = None;
-                            room.drawer_chosen_at = None;
-                            eprintln!("[PLAYER_LEFT] Current drawer left; resetting drawer selection");
-                        }
-                    }
-                    self.state.room.set(Some(room));
-                    if let Some(room) = self.state.room.get() {
-                        self.runtime.emit(format!("game_events_{}", room.room_id).into(), &doodle_game::DoodleEvent::PlayerLeft { 
-                            player_chain_id: player_chain_id.to_string(),
-                            timestamp: timestamp.clone(),
-                        });
-                    }
-                    eprintln!("[PLAYER_LEFT] Player marked as left and host unsubscribed from their events");
-                }
-            }
-            
-            doodle_game::CrossChainMessage::FriendRequest { requester_chain_id } => {
-                let mut received = self.state.friend_requests_received.get().clone();
-                let requester_str = requester_chain_id.to_string();
-                if !received.contains(&requester_str) {
-                    received.push(requester_str);
-                    self.state.friend_requests_received.set(received);
-                }
-            }
-            
-            doodle_game::CrossChainMessage::FriendAccepted { target_chain_id } => {
-                 let target_str = target_chain_id.to_string();
-                 
-                 // Add to friends
-                 let mut friends = self.state.friends.get().clone();
-                 if !friends.contains(&target_str) {
-                     friends.push(target_str.clone());
-                     self.state.friends.set(friends);
-                 }
-                 
-                 // Remove from sent requests
-                 let mut sent = self.state.friend_requests_sent.get().clone();
-                 if let Some(pos) = sent.iter().position(|x| x == &target_str) {
-                     sent.remove(pos);
-                     self.state.friend_requests_sent.set(sent);
-                 }
-            }
-            
-            doodle_game::CrossChainMessage::RoomInvitation { host_chain_id, timestamp } => {
-                let mut invitations = self.state.room_invitations.get().clone();
-                let host_str = host_chain_id.to_string();
-                
-                // Avoid duplicates from same host
-                if !invitations.iter().any(|inv| inv.host_chain_id == host_str) {
-                    invitations.push(Invitation {
-                        host_chain_id: host_str,
-                        timestamp,
-                    });
-                    self.state.room_invitations.set(invitations);
-                }
-            }
-            
-            doodle_game::CrossChainMessage::RoomInvitationCancelled { host_chain_id } => {
-                let mut invitations = self.state.room_invitations.get().clone();
-                let host_str = host_chain_id.to_string();
-                
-                if let Some(pos) = invitations.iter().position(|inv| inv.host_chain_id == host_str) {
-                    invitations.remove(pos);
-                    self.state.room_invitations.set(invitations);
-                }
+            Operation::LeaveMatchQueue => {
+                self.leave_match_queue();
             }
         }
+        
+        self.sync_room_listing();
+        Ok(())
+    }
+
+    async fn execute_message(&mut self, message: Self::Message) {
+        // Host enforces turn and join-approval deadlines whenever anything reaches its inbox
+        self.check_deadlines();
+        self.expire_join_applications();
+        
+        // The sender is whoever the runtime says sent the message
+        let Some(sender) = self.runtime.message_origin_chain_id() else {
+            eprintln!("[MESSAGE] Message without an origin chain - ignoring");
+            return;
+        };
+        if let Err(reason) = self.check_room(&message).and_then(|()| self.check_sender(&message, sender)) {
+            eprintln!("[MESSAGE] Rejected message from {}: {}", sender, reason);
+            return;
+        }
+        
+        self.handle_message(message, sender);
+        self.sync_room_listing();
     }
 
     async fn process_streams(&mut self, streams: Vec<linera_sdk::linera_base_types::StreamUpdate>) {
@@ -811,222 +1764,72 @@ impl Contract for DoodleGameContract {
             }
             
             eprintln!("[STREAM_UPDATE] Processing stream update from chain {:?}", stream_update.chain_id);
+            let source = stream_update.chain_id;
             
             for index in stream_update.previous_index..stream_update.next_index {
                 let stream_name = stream_update.stream_id.stream_name.clone();
-                match self.runtime.read_event(stream_update.chain_id, stream_name, index) {
-                    event => {
-                        match event {
-                            // PlayerJoined event - for existing players when new player joins
-                            // New player gets full state via InitialStateSync
-                            doodle_game::DoodleEvent::PlayerJoined { player, timestamp } => {
-                                eprintln!("[STREAM_UPDATE] Player joined event: {} at {}", player.name, timestamp);
-                                if let Some(mut room) = self.state.room.get().clone() {
-                                    // Add player if not already in list (idempotent)
-                                    if !room.players.iter().any(|p| p.chain_id == player.chain_id) {
-                                        room.add_player(player.clone());
-                                        self.state.room.set(Some(room));
-                                        eprintln!("[STREAM_UPDATE] Player '{}' added to local room state", player.name);
-                                    } else {
-                                        eprintln!("[STREAM_UPDATE] Player '{}' already in room, skipping", player.name);
-                                    }
-                                }
-                            }
-
-                            doodle_game::DoodleEvent::PlayerLeft { player_chain_id, timestamp } => {
-                                eprintln!("[STREAM_UPDATE] Player left event: {} at {}", player_chain_id, timestamp);
-                                if let Some(mut room) = self.state.room.get().clone() {
-                                    let player_index = room.players.iter().position(|p| p.chain_id == player_chain_id);
-                                    if let Some(idx) = player_index {
-                                        if let Some(player) = room.players.get_mut(idx) {
-                                            player.status = PlayerStatus::Left;
-                                            player.has_guessed = false;
-                                        }
-
-                                        if room.current_drawer_index == Some(idx) {
-                                            room.current_drawer_index = None;
-                                            room.game_state = GameState::ChoosingDrawer;
-                                            room.word_chosen_at = None;
-                                            room.drawer_chosen_at = None;
-                                        }
-
-                                        self.state.room.set(Some(room));
-                                    }
-                                }
-                            }
-                            
-                            doodle_game::DoodleEvent::GameStarted { rounds, seconds_per_round, drawer_index, drawer_name, timestamp } => {
-                                eprintln!("[STREAM_UPDATE] Game started: {} rounds, first drawer: {} at {}", rounds, drawer_name, timestamp);
-                                
-                                if let Some(mut room) = self.state.room.get().clone() {
-                                    room.start_game(rounds, seconds_per_round, timestamp.clone());
-                                    room.current_drawer_index = Some(drawer_index);
-                                    room.game_state = GameState::WaitingForWord;
-                                    room.drawer_chosen_at = Some(timestamp);
-                                    // Ensure previous drawing state is cleared at new game start
-                                    room.word_chosen_at = None;
-                                    
-                                    // Reset has_guessed for all players at game start
-                                    for player in &mut room.players {
-                                        player.has_guessed = false;
-                                    }
-                                    
-                                    self.state.room.set(Some(room));
-                                    eprintln!("[STREAM_UPDATE] Game started with drawer and has_guessed reset");
-                                }
-                            }
-                            
-                            doodle_game::DoodleEvent::DrawerChosen { drawer_index, drawer_name, timestamp, .. } => {
-                                eprintln!("[STREAM_UPDATE] Drawer chosen: {} at {}", drawer_name, timestamp);
-                                
-                                if let Some(mut room) = self.state.room.get().clone() {
-                                    room.current_drawer_index = Some(drawer_index);
-                                    room.game_state = GameState::WaitingForWord;
-                                    room.drawer_chosen_at = Some(timestamp);
-                                    // Clear any previous word timing when a new drawer is selected
-                                    room.word_chosen_at = None;
-                                    
-                                    // CRITICAL: Reset has_guessed for all players when new drawer is chosen
-                                    // This ensures point calculation starts fresh (100, 90, 80...)
-                                    for player in &mut room.players {
-                                        player.has_guessed = false;
-                                    }
-                                    
-                                    self.state.room.set(Some(room));
-                                    eprintln!("[STREAM_UPDATE] Drawer updated and has_guessed reset");
-                                }
-                            }
-                            
-                            doodle_game::DoodleEvent::WordChosen { timestamp } => {
-                                eprintln!("[STREAM_UPDATE] Word chosen event from chain {:?} at: {}", stream_update.chain_id, timestamp);
-                                
-                                // FILTER: Only process if event is from current drawer
-                                let is_from_current_drawer = if let Some(room) = self.state.room.get() {
-                                    room.get_current_drawer()
-                                        .map(|drawer| drawer.chain_id == stream_update.chain_id.to_string())
-                                        .unwrap_or(false)
-                                } else {
-                                    false
-                                };
-                                
-                                if !is_from_current_drawer {
-                                    eprintln!("[STREAM_UPDATE] Ignoring WordChosen from non-drawer chain {:?}", stream_update.chain_id);
-                                    return;
-                                }
-                                
-                                // Update local state FIRST
-                                if let Some(mut room) = self.state.room.get().clone() {
-                                    room.word_chosen_at = Some(timestamp.clone());
-                                    room.game_state = GameState::Drawing;
-                                    self.state.room.set(Some(room));
-                                    eprintln!("[STREAM_UPDATE] Word chosen processed in local state");
-                                }
-                                
-                                // THEN re-emit if we're host and event from drawer
-                                let current_chain = self.runtime.chain_id();
-                                if let Some(room) = self.state.room.get() {
-                                    if room.host_chain_id == current_chain.to_string() && stream_update.chain_id != current_chain {
-                                        eprintln!("[STREAM_UPDATE] Host re-emitting WordChosen from drawer to all players");
-                                        if let Some(room) = self.state.room.get() {
-                                            self.runtime.emit(format!("game_events_{}", room.room_id).into(), &doodle_game::DoodleEvent::WordChosen { 
-                                                timestamp: timestamp.clone() 
-                                            });
-                                        }
-                                    }
-                                }
-                            }
-                            
-                            doodle_game::DoodleEvent::ChatMessage { message } => {
-                                eprintln!("[STREAM_UPDATE] Chat message from chain {:?}: {}", stream_update.chain_id, message.player_name);
-                                
-                                // FILTER: Only process if event is from current drawer
-                                let is_from_current_drawer = if let Some(room) = self.state.room.get() {
-                                    room.get_current_drawer()
-                                        .map(|drawer| drawer.chain_id == stream_update.chain_id.to_string())
-                                        .unwrap_or(false)
-                                } else {
-                                    false
-                                };
-                                
-                                if !is_from_current_drawer {
-                                    eprintln!("[STREAM_UPDATE] Ignoring ChatMessage from non-drawer chain {:?}", stream_update.chain_id);
-                                    return;
-                                }
-                                
-                                let current_chain = self.runtime.chain_id();
-                                let is_host = if let Some(room) = self.state.room.get() {
-                                    room.host_chain_id == current_chain.to_string()
-                                } else {
-                                    false
-                                };
-                                
-                                // Process event FIRST (update local state)
-                                if let Some(mut room) = self.state.room.get().clone() {
-                                    // Only add to chat if not already there (check by player_name + message)
-                                    let already_exists = room.chat_messages.iter().any(|m| 
-                                        m.player_name == message.player_name && m.message == message.message
-                                    );
-                                    
-                                    if !already_exists {
-                                        room.add_chat_message(message.clone());
-                                        
-                                        // Update has_guessed flag and points if correct guess
-                                        if message.is_correct_guess {
-                                            for player in &mut room.players {
-                                                if player.name == message.player_name {
-                                                    player.has_guessed = true;
-                                                    player.score += message.points_awarded;
-                                                    eprintln!("[STREAM_UPDATE] Player '{}' score updated: +{} points", player.name, message.points_awarded);
-                                                    break;
-                                                }
-                                            }
-                                        }
-                                        
-                                        self.state.room.set(Some(room));
-                                        eprintln!("[STREAM_UPDATE] Chat message processed and added to local state");
-                                    } else {
-                                        eprintln!("[STREAM_UPDATE] Chat message already exists - skipping duplicate");
-                                    }
-                                }
-                                
-                                // THEN re-emit if we're host and event came from drawer
-                                if is_host && stream_update.chain_id != current_chain {
-                                    eprintln!("[STREAM_UPDATE] Host re-emitting ChatMessage from drawer {:?} to all players", stream_update.chain_id);
-                                    if let Some(room) = self.state.room.get() {
-                                        self.runtime.emit(format!("game_events_{}", room.room_id).into(), &doodle_game::DoodleEvent::ChatMessage { 
-                                            message: message.clone() 
-                                        });
-                                    }
-                                }
-                            }
-                            
-                            doodle_game::DoodleEvent::RoundEnded { scores: _, timestamp } => {
-                                eprintln!("[STREAM_UPDATE] Round ended at {}", timestamp);
-                                if let Some(mut room) = self.state.room.get().clone() {
-                                    room.advance_to_next_round(timestamp.clone());
-                                    self.state.room.set(Some(room));
-                                    eprintln!("[STREAM_UPDATE] Round advanced, waiting for drawer selection");
-                                }
-                            }
-                            
-                            doodle_game::DoodleEvent::GameEnded { final_scores: _, timestamp } => {
-                                eprintln!("[STREAM_UPDATE] Game ended at {}", timestamp);
-                                if let Some(mut room) = self.state.room.get().clone() {
-                                    room.game_state = GameState::GameEnded;
-                                    self.state.room.set(Some(room));
-                                    eprintln!("[STREAM_UPDATE] Game ended in local room state");
-                                }
-                            }
-                            
-                            doodle_game::DoodleEvent::MatchEnded { timestamp } => {
-                                eprintln!("[STREAM_UPDATE] Match ended at {}", timestamp);
-                                // Room will be deleted via RoomDeleted message
-                            }
+                let event = self.runtime.read_event(stream_update.chain_id, stream_name, index);
+                eprintln!("[STREAM_UPDATE] {} event from chain {:?}", event.name(), stream_update.chain_id);
+                
+                let Some(mut room) = self.state.room.get().clone() else {
+                    continue;
+                };
+                
+                // FILTER: Drop events from chains that may not send them
+                if !Self::is_trusted_source(&room, source, &event) {
+                    eprintln!("[STREAM_UPDATE] Ignoring {} from untrusted chain {:?}", event.name(), source);
+                    self.record_rejected_event(source, &event);
+                    continue;
+                }
+                
+                // Process event FIRST (same reducer the emitting chain used)
+                if let Err(error) = room.apply(&event) {
+                    eprintln!("[STREAM_UPDATE] Rejected {}: {}", event.name(), error);
+                    continue;
+                }
+                
+                // Local effects that depend on this chain's private state
+                match &event {
+                    doodle_game::DoodleEvent::GameStarted { .. } => self.prepare_local_turn(&room),
+                    doodle_game::DoodleEvent::DrawerChosen { .. } => {
+                        // Previous drawer's turn is over; reveal our word if we were drawing
+                        self.reveal_pending_word(&mut room);
+                        self.prepare_local_turn(&room);
+                    }
+                    doodle_game::DoodleEvent::RoundEnded { .. } | doodle_game::DoodleEvent::GameEnded { .. } => {
+                        self.reveal_pending_word(&mut room);
+                    }
+                    doodle_game::DoodleEvent::ChatMessage { .. } => self.mark_own_close_guesses(&mut room),
+                    doodle_game::DoodleEvent::WordRevealed { commitment, .. } => {
+                        let status = room.turn_audits.iter().rev().find(|turn| &turn.commitment == commitment).map(|turn| turn.status);
+                        match status {
+                            Some(AuditStatus::Verified) => eprintln!("[STREAM_UPDATE] Turn audit verified"),
+                            status => eprintln!("[STREAM_UPDATE] AUDIT FAILED for commitment {}: {:?}", commitment, status),
                         }
                     }
+                    _ => {}
+                }
+                
+                let is_host = room.host_chain_id == current_chain;
+                let is_turn_complete = matches!(event, doodle_game::DoodleEvent::TurnComplete { .. });
+                self.state.room.set(Some(room.clone()));
+                
+                // THEN re-emit if we're host: players only subscribe to the host, so it relays the drawer's events
+                if is_host && Self::is_relayed(&event) {
+                    eprintln!("[STREAM_UPDATE] Host re-emitting {} from chain {:?} to all players", event.name(), stream_update.chain_id);
+                    self.runtime.emit(room.stream_name(), &event);
+                }
+                
+                // Host answers the drawer's turn-complete signal by advancing, if our own state agrees
+                if is_host && is_turn_complete && room.settings.ends_turn_early() && room.all_guessers_done() {
+                    if let Err(error) = self.advance_turn(TurnEndReason::AllGuessed) {
+                        eprintln!("[STREAM_UPDATE] ERROR: {}", error);
+                    }
                 }
             }
         }
+        
+        self.sync_room_listing();
     }
 
     async fn store(mut self) {
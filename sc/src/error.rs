@@ -0,0 +1,134 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+/*! Errors returned by operations */
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::GameState;
+
+// Why an operation was rejected (returned as the operation response)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DoodleError {
+    /// This chain is not in a room
+    NoActiveRoom,
+    /// Only the host chain can do this
+    NotHost,
+    /// Only the current drawer can do this
+    NotDrawer,
+    /// Only active players in the room can do this
+    NotActivePlayer,
+    /// The drawer can't guess their own word
+    DrawerCannotGuess,
+    /// The room is not in the state the operation needs
+    WrongGameState { expected: GameState, actual: GameState },
+    /// Event or message is for a room this chain is not in
+    WrongRoom(String),
+    /// No active player could be picked to draw
+    NoActivePlayers,
+    /// String is not a valid chain ID
+    InvalidChainId(String),
+    /// String is not a valid blob hash
+    InvalidBlobHash(String),
+    /// Room settings don't fit the chosen word bank
+    InvalidSettings(String),
+    /// Blob is not a valid word pack
+    InvalidWordPack(String),
+    /// Word commitment needs a secret salt from the drawer's client
+    MissingSalt,
+    /// Drawer tried to pick a word it wasn't offered
+    WordNotOffered(String),
+    /// No pending friend request from this chain
+    NoFriendRequest(String),
+    /// Only friends can be invited
+    NotAFriend(String),
+    /// No join request from this chain is waiting for approval
+    NoJoinRequest(String),
+    /// This chain does not keep the public room directory
+    NotRegistry,
+    /// The application was created without a registry chain
+    NoRegistry,
+    /// Leave the current room first
+    AlreadyInRoom,
+    /// This chain is not waiting for a quick-play match
+    NotQueued,
+    /// No pending invitation from this host
+    NoInvitation(String),
+    /// Invitation is older than five minutes
+    InvitationExpired(String),
+    /// Event is not allowed in the room's current state
+    IllegalTransition { event: String, state: GameState },
+    /// No player with this chain ID
+    UnknownPlayer(String),
+    /// Drawer index doesn't point at an active player (or the current drawer)
+    InvalidDrawer(usize),
+    /// The host can't kick or ban itself
+    CannotKickHost,
+    /// The host banned this chain from the room
+    Banned,
+    /// The room has no seat left
+    RoomFull,
+    /// The room is private and the host did not invite this chain
+    NotInvited,
+    /// The room password doesn't match
+    WrongPassword,
+    /// Only players who were already in the room can join a running game
+    GameAlreadyStarted,
+    /// Another player in the room already uses this name
+    NameTaken(String),
+    /// Player already found the word this turn
+    AlreadyGuessed(String),
+    /// Reveal doesn't match any recorded turn
+    UnknownCommitment(String),
+    /// Hint reveals fewer letters than the current one
+    StaleHint,
+}
+
+impl fmt::Display for DoodleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DoodleError::NoActiveRoom => write!(f, "Not in a room"),
+            DoodleError::NotHost => write!(f, "Only the host can do this"),
+            DoodleError::NotDrawer => write!(f, "Only the current drawer can do this"),
+            DoodleError::NotActivePlayer => write!(f, "Only active players in the room can do this"),
+            DoodleError::DrawerCannotGuess => write!(f, "The drawer can't guess their own word"),
+            DoodleError::WrongGameState { expected, actual } => {
+                write!(f, "Room must be in state {:?} but is {:?}", expected, actual)
+            }
+            DoodleError::WrongRoom(room_id) => write!(f, "Not in room {}", room_id),
+            DoodleError::NoActivePlayers => write!(f, "No active players to draw"),
+            DoodleError::InvalidChainId(id) => write!(f, "Invalid chain ID '{}'", id),
+            DoodleError::InvalidBlobHash(hash) => write!(f, "Invalid blob hash '{}'", hash),
+            DoodleError::InvalidSettings(reason) => write!(f, "Invalid room settings: {}", reason),
+            DoodleError::InvalidWordPack(reason) => write!(f, "Invalid word pack: {}", reason),
+            DoodleError::MissingSalt => write!(f, "A random salt is required to commit to the word"),
+            DoodleError::WordNotOffered(word) => write!(f, "Word '{}' was not offered to this drawer", word),
+            DoodleError::NoFriendRequest(id) => write!(f, "No friend request from {}", id),
+            DoodleError::NotAFriend(id) => write!(f, "{} is not a friend", id),
+            DoodleError::NoJoinRequest(id) => write!(f, "No join request from {} is waiting for approval", id),
+            DoodleError::NotRegistry => write!(f, "This chain is not the room registry"),
+            DoodleError::NoRegistry => write!(f, "No registry chain is configured"),
+            DoodleError::AlreadyInRoom => write!(f, "Already in a room"),
+            DoodleError::NotQueued => write!(f, "Not queued for a match"),
+            DoodleError::NoInvitation(id) => write!(f, "No invitation from {}", id),
+            DoodleError::InvitationExpired(id) => write!(f, "Invitation from {} has expired", id),
+            DoodleError::IllegalTransition { event, state } => write!(f, "{} is not allowed in state {:?}", event, state),
+            DoodleError::UnknownPlayer(id) => write!(f, "Unknown player {}", id),
+            DoodleError::InvalidDrawer(index) => write!(f, "Player {} cannot draw now", index),
+            DoodleError::CannotKickHost => write!(f, "The host can't be kicked or banned"),
+            DoodleError::Banned => write!(f, "Banned from this room"),
+            DoodleError::RoomFull => write!(f, "The room is full"),
+            DoodleError::NotInvited => write!(f, "The room is private"),
+            DoodleError::WrongPassword => write!(f, "Wrong room password"),
+            DoodleError::GameAlreadyStarted => write!(f, "The game has already started"),
+            DoodleError::NameTaken(name) => write!(f, "Name '{}' is already taken in this room", name),
+            DoodleError::AlreadyGuessed(id) => write!(f, "{} already guessed the word", id),
+            DoodleError::UnknownCommitment(commitment) => write!(f, "No turn with commitment {}", commitment),
+            DoodleError::StaleHint => write!(f, "Hint is older than the current one"),
+        }
+    }
+}
+
+impl std::error::Error for DoodleError {}
@@ -0,0 +1,229 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+/*! Guess normalization and similarity scoring */
+
+use serde::{Deserialize, Serialize};
+
+// Outcome of comparing a guess with the word
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum GuessVerdict {
+    Correct,
+    /// Wrong, but within a small edit distance of the word
+    Close,
+    Wrong,
+}
+
+/// Fold case and diacritics, turn punctuation into spaces and collapse whitespace
+/// ("  Crème-Brûlée!! " -> "creme brulee")
+pub fn normalize(text: &str) -> String {
+    let mut folded = String::with_capacity(text.len());
+    for c in text.chars().flat_map(char::to_lowercase) {
+        match fold_char(c) {
+            Some(base) => folded.push_str(base),
+            None if c.is_alphanumeric() => folded.push(c),
+            None => folded.push(' '),
+        }
+    }
+    folded.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Canonical form used for equality: normalized, without separators
+/// ("Ice-cream" and "ice cream" both give "icecream")
+pub fn match_key(text: &str) -> String {
+    normalize(text).chars().filter(|c| !c.is_whitespace()).collect()
+}
+
+/// Keys a guess may match: its own key, then the word it could be the plural of
+/// ("icecreams" gives "icecreams" and "icecream"). Only the guess is de-pluralized,
+/// so guessing "new" never matches "news"
+pub fn guess_keys(guess: &str) -> Vec<String> {
+    let key = match_key(guess);
+    if key.is_empty() {
+        return Vec::new();
+    }
+    let mut keys = vec![key.clone()];
+    keys.extend(singulars(&key).into_iter().filter(|stem| *stem != key));
+    keys
+}
+
+/// Compare a guess with the word
+pub fn check_guess(word: &str, guess: &str) -> GuessVerdict {
+    let word_key = match_key(word);
+    let guess_keys = guess_keys(guess);
+    if guess_keys.is_empty() {
+        return GuessVerdict::Wrong;
+    }
+    if guess_keys.contains(&word_key) {
+        return GuessVerdict::Correct;
+    }
+    let distance = guess_keys.iter().map(|key| edit_distance(&word_key, key)).min().unwrap_or(usize::MAX);
+    if distance <= close_threshold(&word_key) {
+        GuessVerdict::Close
+    } else {
+        GuessVerdict::Wrong
+    }
+}
+
+// Allowed typos before a guess stops being "close"
+fn close_threshold(word_key: &str) -> usize {
+    match word_key.chars().count() {
+        0..=3 => 0,
+        4..=6 => 1,
+        _ => 2,
+    }
+}
+
+/// Levenshtein distance over characters
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+// Singular forms a key could be the simple English plural of ("cities" -> "city", "boxes" -> "box")
+// Words ending in "ss", "us" or "is" are left alone: "glass", "cactus", "paris" are already singular
+fn singulars(key: &str) -> Vec<String> {
+    let mut stems = Vec::new();
+    if key.chars().count() <= 3 {
+        return stems;
+    }
+    if let Some(stem) = key.strip_suffix("ies") {
+        if stem.chars().count() >= 2 {
+            stems.push(format!("{}y", stem));
+        }
+    }
+    if let Some(stem) = key.strip_suffix("es") {
+        if ["s", "x", "z", "ch", "sh"].iter().any(|ending| stem.ends_with(ending)) {
+            stems.push(stem.to_string());
+        }
+    }
+    if let Some(stem) = key.strip_suffix('s') {
+        if !["s", "u", "i"].iter().any(|ending| stem.ends_with(ending)) {
+            stems.push(stem.to_string());
+        }
+    }
+    stems
+}
+
+// Case-folding extras and diacritic stripping for Latin and Cyrillic letters
+fn fold_char(c: char) -> Option<&'static str> {
+    let base = match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'æ' => "ae",
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+        'ď' | 'đ' | 'ð' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+        'ĥ' | 'ħ' => "h",
+        'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+        'ĵ' => "j",
+        'ķ' => "k",
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+        'ñ' | 'ń' | 'ņ' | 'ň' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+        'œ' => "oe",
+        'ŕ' | 'ŗ' | 'ř' => "r",
+        'ś' | 'ŝ' | 'ş' | 'š' => "s",
+        'ß' => "ss",
+        'ţ' | 'ť' | 'ŧ' => "t",
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+        'ŵ' => "w",
+        'ý' | 'ÿ' | 'ŷ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        'ё' => "е",
+        'ς' => "σ",
+        // Apostrophes join words ("director's" -> "directors") instead of splitting them
+        '\'' | '’' | 'ʼ' => "",
+        _ => return None,
+    };
+    Some(base)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_case_diacritics_and_separators() {
+        assert_eq!(normalize("  Crème-Brûlée!! "), "creme brulee");
+        assert_eq!(normalize("Director's   CUT"), "directors cut");
+        assert_eq!(normalize("Ёлка"), "елка");
+        assert_eq!(match_key("Ice-cream"), "icecream");
+        assert_eq!(match_key("ice cream"), "icecream");
+        assert_eq!(match_key("Straße"), "strasse");
+        assert_eq!(match_key("?!"), "");
+    }
+
+    #[test]
+    fn hyphens_spaces_and_diacritics_do_not_matter() {
+        assert_eq!(check_guess("ice cream", "Ice-Cream"), GuessVerdict::Correct);
+        assert_eq!(check_guess("icecream", "ice cream"), GuessVerdict::Correct);
+        assert_eq!(check_guess("crème brûlée", "creme brulee"), GuessVerdict::Correct);
+        assert_eq!(check_guess("cafe", "Café!"), GuessVerdict::Correct);
+    }
+
+    #[test]
+    fn plural_guesses_match_the_singular_word() {
+        assert_eq!(check_guess("cat", "cats"), GuessVerdict::Correct);
+        assert_eq!(check_guess("ice cream", "ice creams"), GuessVerdict::Correct);
+        assert_eq!(check_guess("box", "boxes"), GuessVerdict::Correct);
+        assert_eq!(check_guess("church", "churches"), GuessVerdict::Correct);
+        assert_eq!(check_guess("city", "cities"), GuessVerdict::Correct);
+        assert_eq!(check_guess("pie", "pies"), GuessVerdict::Correct);
+    }
+
+    #[test]
+    fn singular_words_are_not_stripped() {
+        // "paris" is not the plural of "pari", and dropping the "s" of the word is not allowed
+        assert_eq!(check_guess("paris", "pari"), GuessVerdict::Close);
+        assert_eq!(check_guess("pari", "paris"), GuessVerdict::Close);
+        assert_eq!(check_guess("news", "new"), GuessVerdict::Close);
+        assert_eq!(check_guess("glass", "glas"), GuessVerdict::Close);
+        assert_eq!(check_guess("cactus", "cactu"), GuessVerdict::Close);
+        assert_eq!(check_guess("cats", "cat"), GuessVerdict::Close);
+        assert_eq!(guess_keys("bus"), vec!["bus".to_string()]);
+        assert_eq!(guess_keys("glass"), vec!["glass".to_string()]);
+    }
+
+    #[test]
+    fn close_threshold_grows_with_the_word() {
+        // Up to three letters: exact only
+        assert_eq!(check_guess("cat", "car"), GuessVerdict::Wrong);
+        // Four to six letters: one typo
+        assert_eq!(check_guess("house", "hause"), GuessVerdict::Close);
+        assert_eq!(check_guess("house", "haust"), GuessVerdict::Wrong);
+        // Longer words: two typos
+        assert_eq!(check_guess("elephant", "elefant"), GuessVerdict::Close);
+        assert_eq!(check_guess("elephant", "elefint"), GuessVerdict::Wrong);
+    }
+
+    #[test]
+    fn unrelated_guesses_are_wrong() {
+        assert_eq!(check_guess("apple", "banana"), GuessVerdict::Wrong);
+        assert_eq!(check_guess("apple", ""), GuessVerdict::Wrong);
+        assert_eq!(check_guess("apple", "!!!"), GuessVerdict::Wrong);
+        assert_eq!(check_guess("sun", "sunflower"), GuessVerdict::Wrong);
+        assert_eq!(check_guess("sunflower", "sun"), GuessVerdict::Wrong);
+    }
+
+    #[test]
+    fn edit_distance_counts_single_character_edits() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("abc", ""), 3);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("flaw", "lawn"), 2);
+        assert_eq!(edit_distance("ёлка", "елка"), 1);
+    }
+}
@@ -0,0 +1,112 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+/*! Progressive letter hints shown to guessers during the drawing phase */
+
+use linera_sdk::linera_base_types::Timestamp;
+use serde::{Deserialize, Serialize};
+
+use crate::word_bank::{next_random, seed_from};
+
+// Percent of the drawing time after which one more letter is revealed
+pub const HINT_SCHEDULE_PERCENT: [u64; 3] = [40, 60, 80];
+
+// Masked word broadcast by the drawer ("_c_ ___a_")
+#[derive(Debug, Clone, Serialize, Deserialize, async_graphql::SimpleObject)]
+#[graphql(rename_fields = "camelCase")]
+pub struct WordHint {
+    pub mask: String,
+    pub revealed_letters: u32,
+    pub timestamp: Timestamp,
+}
+
+/// Never reveal more than a third of the letters
+pub fn max_reveals(word: &str) -> u32 {
+    (word.chars().filter(|c| c.is_alphanumeric()).count() / 3) as u32
+}
+
+/// Number of letters due `elapsed_micros` after the word was chosen
+pub fn due_reveals(word: &str, elapsed_micros: u64, seconds_per_round: u32) -> u32 {
+    let duration_micros = seconds_per_round as u64 * 1_000_000;
+    if duration_micros == 0 {
+        return 0;
+    }
+    let scheduled = HINT_SCHEDULE_PERCENT.iter()
+        .filter(|percent| elapsed_micros.saturating_mul(100) >= duration_micros * **percent)
+        .count() as u32;
+    scheduled.min(max_reveals(word))
+}
+
+/// Replace unrevealed letters with '_' and keep spaces and punctuation
+/// The same seed reveals letters in the same order, so each hint extends the previous one
+pub fn mask_word(word: &str, revealed: u32, seed: &str) -> String {
+    let mut hidden: Vec<usize> = word.chars()
+        .enumerate()
+        .filter(|(_, c)| c.is_alphanumeric())
+        .map(|(index, _)| index)
+        .collect();
+
+    let mut state = seed_from(seed);
+    let mut shown = Vec::new();
+    while shown.len() < revealed as usize && !hidden.is_empty() {
+        let pick = (next_random(&mut state) % hidden.len() as u64) as usize;
+        shown.push(hidden.swap_remove(pick));
+    }
+
+    word.chars()
+        .enumerate()
+        .map(|(index, c)| if !c.is_alphanumeric() || shown.contains(&index) { c } else { '_' })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECOND: u64 = 1_000_000;
+
+    #[test]
+    fn reveals_at_most_a_third_of_the_letters() {
+        assert_eq!(max_reveals("ab"), 0);
+        assert_eq!(max_reveals("cat"), 1);
+        assert_eq!(max_reveals("ice cream"), 2);
+        assert_eq!(max_reveals("elephants"), 3);
+    }
+
+    #[test]
+    fn hints_follow_the_schedule() {
+        assert_eq!(due_reveals("elephants", 0, 100), 0);
+        assert_eq!(due_reveals("elephants", 39 * SECOND, 100), 0);
+        assert_eq!(due_reveals("elephants", 40 * SECOND, 100), 1);
+        assert_eq!(due_reveals("elephants", 60 * SECOND, 100), 2);
+        assert_eq!(due_reveals("elephants", 80 * SECOND, 100), 3);
+        assert_eq!(due_reveals("elephants", 500 * SECOND, 100), 3);
+        // Capped by the word length, and nothing without a drawing time
+        assert_eq!(due_reveals("cat", 90 * SECOND, 100), 1);
+        assert_eq!(due_reveals("ab", 90 * SECOND, 100), 0);
+        assert_eq!(due_reveals("elephants", 90 * SECOND, 0), 0);
+    }
+
+    #[test]
+    fn masks_letters_and_keeps_separators() {
+        assert_eq!(mask_word("ice cream", 0, "seed"), "___ _____");
+        assert_eq!(mask_word("t-rex!", 0, "seed"), "_-___!");
+        assert_eq!(mask_word("cat", 10, "seed"), "cat");
+
+        let one = mask_word("ice cream", 1, "seed");
+        assert_eq!(one.chars().filter(|c| c.is_alphanumeric()).count(), 1);
+        assert_eq!(one, mask_word("ice cream", 1, "seed"));
+    }
+
+    #[test]
+    fn each_hint_extends_the_previous_one() {
+        let word = "watermelon";
+        let mut previous = mask_word(word, 0, "room:1:2");
+        for revealed in 1..=3 {
+            let next = mask_word(word, revealed, "room:1:2");
+            assert!(previous.chars().zip(next.chars()).all(|(before, after)| before == '_' || before == after));
+            assert_eq!(next.chars().filter(|c| *c != '_').count(), revealed as usize);
+            previous = next;
+        }
+    }
+}
@@ -3,15 +3,26 @@
 
 /*! ABI of the Doodle Game Application */
 
+pub mod error;
+pub mod guess;
+pub mod hint;
+pub mod registry;
+pub mod word_bank;
+
+use std::str::FromStr;
+
 use async_graphql::{Request, Response};
-use linera_sdk::linera_base_types::{ContractAbi, ServiceAbi};
+use linera_sdk::linera_base_types::{BcsHashable, ChainId, ContractAbi, CryptoHash, ServiceAbi, StreamName, TimeDelta, Timestamp};
 use serde::{Deserialize, Serialize};
+pub use error::DoodleError;
+use hint::WordHint;
+use word_bank::{Difficulty, WordBank, DEFAULT_WORD_BANK, DEFAULT_WORD_CHOICES, MAX_WORD_CHOICES};
 
 pub struct DoodleGameAbi;
 
 impl ContractAbi for DoodleGameAbi {
     type Operation = Operation;
-    type Response = ();
+    type Response = Result<(), DoodleError>;
 }
 
 impl ServiceAbi for DoodleGameAbi {
@@ -19,11 +30,19 @@ impl ServiceAbi for DoodleGameAbi {
     type QueryResponse = Response;
 }
 
+// Application parameters, the same on every chain
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DoodleParameters {
+    pub registry_chain_id: Option<ChainId>, // Chain listing public rooms (see `registry`); None disables the directory
+}
+
 // Player structure
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, async_graphql::Enum, PartialEq, Eq)]
 pub enum PlayerStatus {
     Active,
     Left,
+    /// Removed by the host
+    Kicked,
 }
 
 impl Default for PlayerStatus {
@@ -35,7 +54,7 @@ impl Default for PlayerStatus {
 #[derive(Debug, Clone, Serialize, Deserialize, async_graphql::SimpleObject)]
 #[graphql(rename_fields = "camelCase")]
 pub struct Player {
-    pub chain_id: String,
+    pub chain_id: ChainId,
     pub name: String,
     #[serde(default)]
     pub avatar_json: String,
@@ -64,17 +83,435 @@ pub struct DrawPointOutput {
 #[graphql(rename_fields = "camelCase")]
 pub struct GameRoom {
     pub room_id: String,
-    pub host_chain_id: String,
+    pub host_chain_id: ChainId,
     pub players: Vec<Player>,
     pub game_state: GameState,
     pub current_round: u32,
     pub total_rounds: u32,
     pub seconds_per_round: u32,
     pub current_drawer_index: Option<usize>,
-    pub word_chosen_at: Option<String>,  // NEEDED for drawing timer
+    pub word_chosen_at: Option<Timestamp>,  // NEEDED for drawing timer
     pub chat_messages: Vec<ChatMessage>,
-    pub drawer_chosen_at: Option<String>, // NEEDED for word selection timer
+    pub drawer_chosen_at: Option<Timestamp>, // NEEDED for word selection timer
     pub blob_hashes: Vec<String>, // History of all drawings in the room
+    #[serde(default)]
+    pub settings: RoomSettings,
+    #[serde(default)]
+    pub word_hint: Option<WordHint>, // Letter hint for guessers, updated as the turn progresses
+    #[serde(default)]
+    pub turn_guess_millis: Vec<u64>, // Elapsed time of each correct guess this turn (drawer scoring)
+    #[serde(default)]
+    pub chat_sequence: u64, // Sequence of the last chat message applied this turn
+    #[serde(default)]
+    pub current_turn: Option<TurnAudit>, // Commitment + scored guesses of the turn in progress
+    #[serde(default)]
+    pub turn_audits: Vec<TurnAudit>, // Finished turns, checked when the drawer reveals the word
+    #[serde(default)]
+    pub auto_pick_requested_at: Option<Timestamp>, // Host asked the drawer's chain to pick a word (AutoPick rooms)
+    #[serde(default)]
+    pub banned: Vec<ChainId>, // Chains the host banned; their join requests are refused
+}
+
+// Where guesses are sent and scored
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, async_graphql::Enum, PartialEq, Eq)]
+pub enum GuessValidation {
+    /// Guesses go to the drawer's chain, which knows the plaintext word
+    Drawer,
+    /// Guesses go to the host chain, which checks them against a salted hash of the word
+    Host,
+}
+
+impl Default for GuessValidation {
+    fn default() -> Self {
+        GuessValidation::Drawer
+    }
+}
+
+// What the host does when the drawer doesn't pick a word in time
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, async_graphql::Enum, PartialEq, Eq)]
+pub enum WordTimeoutAction {
+    /// Move on to the next drawer
+    Skip,
+    /// Ask the drawer's chain to pick one of its candidates (skips if it doesn't answer in time)
+    AutoPick,
+}
+
+impl Default for WordTimeoutAction {
+    fn default() -> Self {
+        WordTimeoutAction::Skip
+    }
+}
+
+// Who may join a room
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, async_graphql::Enum, PartialEq, Eq)]
+pub enum RoomVisibility {
+    /// Any chain that knows the host chain ID
+    Public,
+    /// Only chains the host invited
+    Private,
+}
+
+impl Default for RoomVisibility {
+    fn default() -> Self {
+        RoomVisibility::Public
+    }
+}
+
+// Room options chosen by the host at CreateRoom and editable in the lobby
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, async_graphql::SimpleObject, async_graphql::InputObject)]
+#[graphql(rename_fields = "camelCase", input_name = "RoomSettingsInput")]
+pub struct RoomSettings {
+    #[serde(default)]
+    pub guess_validation: GuessValidation,
+    #[serde(default)]
+    pub word_bank: Option<String>, // Built-in bank id, defaults to DEFAULT_WORD_BANK
+    #[serde(default)]
+    pub word_category: Option<String>,
+    #[serde(default)]
+    pub word_difficulty: Option<Difficulty>,
+    #[serde(default)]
+    pub word_choices: Option<u32>, // Candidates offered to the drawer each turn
+    #[serde(default)]
+    pub word_pack: Option<String>, // Data blob hash of a custom word pack, overrides word_bank
+    #[serde(default)]
+    pub scoring: Option<ScoringRules>, // Defaults to ScoringRules::default()
+    #[serde(default)]
+    pub word_selection_seconds: Option<u32>, // Defaults to DEFAULT_WORD_SELECTION_SECONDS
+    #[serde(default)]
+    pub word_timeout_action: WordTimeoutAction,
+    #[serde(default)]
+    pub end_turn_when_all_guessed: Option<bool>, // Defaults to true
+    #[serde(default)]
+    pub max_players: Option<u32>, // Defaults to DEFAULT_MAX_PLAYERS
+    #[serde(default)]
+    pub visibility: RoomVisibility,
+    #[serde(default)]
+    #[graphql(skip)]
+    pub password_hash: Option<String>, // See `password_hash`; set by the host from the password in CreateRoom/UpdateSettings
+    #[serde(default)]
+    pub language: Option<String>, // Language code players should guess in, e.g. "en"
+    #[serde(default)]
+    pub approval_required: bool, // Join requests wait in the host's queue until approved
+}
+
+// Guesser scoring weights: order of the guess and time since the word was chosen
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, async_graphql::SimpleObject, async_graphql::InputObject)]
+#[graphql(rename_fields = "camelCase", input_name = "ScoringRulesInput")]
+pub struct ScoringRules {
+    pub max_points: u32, // First guesser, right after the word was chosen
+    pub min_points: u32, // Floor for any correct guess
+    pub order_penalty: u32, // Points lost per player who guessed earlier
+    pub time_weight_percent: u32, // Share of max_points that decays linearly over the turn (0-100)
+    pub drawer_max_points: u32, // Drawer's award when everyone guesses instantly
+    pub drawer_miss_penalty: u32, // Drawer's loss when nobody guesses
+}
+
+impl Default for ScoringRules {
+    fn default() -> Self {
+        ScoringRules {
+            max_points: 100,
+            min_points: 10,
+            order_penalty: 10,
+            time_weight_percent: 50,
+            drawer_max_points: 100,
+            drawer_miss_penalty: 20,
+        }
+    }
+}
+
+impl ScoringRules {
+    /// Points for a correct guess by the `guessed_before + 1`-th guesser, `elapsed_millis` into the turn
+    pub fn guesser_points(&self, guessed_before: u32, elapsed_millis: u64, seconds_per_round: u32) -> u32 {
+        let max_points = self.max_points as u64;
+        let time_share = max_points * self.time_weight_percent.min(100) as u64 / 100;
+        let duration_millis = seconds_per_round as u64 * 1_000;
+        let time_bonus = if duration_millis == 0 {
+            time_share
+        } else {
+            time_share * duration_millis.saturating_sub(elapsed_millis) / duration_millis
+        };
+
+        let points = (max_points - time_share + time_bonus)
+            .saturating_sub(self.order_penalty as u64 * guessed_before as u64);
+        (points as u32).max(self.min_points)
+    }
+
+    /// Score change for the drawer when the turn ends
+    /// Scales with the share of `eligible` players who guessed and how fast they did (`guess_millis`)
+    pub fn drawer_points(&self, guess_millis: &[u64], eligible: u32, seconds_per_round: u32) -> i64 {
+        let guessed = guess_millis.len() as u64;
+        if guessed == 0 {
+            return -(self.drawer_miss_penalty as i64);
+        }
+
+        // Per-mille averages so everything stays in integers
+        let duration_millis = seconds_per_round as u64 * 1_000;
+        let speed: u64 = guess_millis.iter()
+            .map(|millis| if duration_millis == 0 { 1_000 } else { 1_000 * duration_millis.saturating_sub(*millis) / duration_millis })
+            .sum::<u64>() / guessed;
+        let share = 1_000 * guessed / (eligible as u64).max(guessed);
+
+        // Everyone guessing slowly still earns half of the share-weighted award
+        (self.drawer_max_points as u64 * share * (1_000 + speed) / 2_000_000) as i64
+    }
+}
+
+impl RoomSettings {
+    pub fn word_bank_id(&self) -> &str {
+        self.word_bank.as_deref().unwrap_or(DEFAULT_WORD_BANK)
+    }
+
+    pub fn scoring(&self) -> ScoringRules {
+        self.scoring.clone().unwrap_or_default()
+    }
+
+    pub fn word_choices(&self) -> u32 {
+        self.word_choices.unwrap_or(DEFAULT_WORD_CHOICES).clamp(1, MAX_WORD_CHOICES)
+    }
+
+    pub fn ends_turn_early(&self) -> bool {
+        self.end_turn_when_all_guessed.unwrap_or(true)
+    }
+
+    pub fn word_selection_seconds(&self) -> u32 {
+        self.word_selection_seconds
+            .unwrap_or(DEFAULT_WORD_SELECTION_SECONDS)
+            .clamp(MIN_WORD_SELECTION_SECONDS, MAX_WORD_SELECTION_SECONDS)
+    }
+
+    pub fn max_players(&self) -> u32 {
+        self.max_players.unwrap_or(DEFAULT_MAX_PLAYERS).clamp(MIN_PLAYERS, MAX_PLAYERS)
+    }
+
+    /// Capacity must fit the players already in the room
+    pub fn check_capacity(&self, players: u32) -> Result<(), DoodleError> {
+        if self.max_players() < players {
+            return Err(DoodleError::InvalidSettings(format!(
+                "Room has {} players, more than the maximum of {}", players, self.max_players()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Rooms without a password accept any (or no) password
+    pub fn password_matches(&self, room_id: &str, password: Option<&str>) -> bool {
+        match &self.password_hash {
+            Some(hash) => password.map_or(false, |password| &password_hash(room_id, password) == hash),
+            None => true,
+        }
+    }
+
+    /// Check that the chosen category exists in the resolved bank or pack
+    pub fn validate(&self, bank: &WordBank) -> Result<(), String> {
+        if let Some(category) = &self.word_category {
+            if !bank.categories().contains(category) {
+                return Err(format!("Unknown category '{}' in word bank '{}'", category, bank.id));
+            }
+        }
+        if bank.filtered(self.word_category.as_deref(), self.word_difficulty).is_empty() {
+            return Err(format!("No words in word bank '{}' match the category and difficulty filters", bank.id));
+        }
+        Ok(())
+    }
+}
+
+// Salted hash of the normalized word that lets the host score guesses without being told the word
+// The drawer sends it to the host chain only; it is never replicated or exposed over GraphQL.
+// The message is still readable in the host's blocks, and the word can be recovered by hashing
+// every word of the bank or pack with this salt, so host-validated rooms trust the host not to look.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuessCheck {
+    pub commitment: String, // Turn the check belongs to (the WordChosen commitment)
+    pub salt: String,
+    pub hash: String,
+}
+
+impl GuessCheck {
+    pub fn new(word: &str, salt: String, commitment: String) -> Self {
+        Self {
+            commitment,
+            hash: word_commitment(word, &salt),
+            salt,
+        }
+    }
+
+    /// Check a guess by hashing it (and the word it could be the plural of) the same way as the word
+    pub fn matches(&self, guess: &str) -> bool {
+        guess::guess_keys(guess).iter().any(|key| word_commitment(key, &self.salt) == self.hash)
+    }
+}
+
+// Hash preimage of a guess check salt
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct GuessCheckSaltPreimage {
+    word_salt: String,
+}
+
+impl BcsHashable<'_> for GuessCheckSaltPreimage {}
+
+/// Salt of the drawer's `GuessCheck`, derived from the secret salt of the word commitment
+/// Nobody can compute it before the drawer sends it, and it doesn't open the commitment itself
+pub fn guess_check_salt(word_salt: &str) -> String {
+    CryptoHash::new(&GuessCheckSaltPreimage { word_salt: word_salt.to_string() }).to_string()
+}
+
+// Maximum number of finished turns kept for auditing
+pub const MAX_TURN_AUDITS: usize = 20;
+
+// Outcome of checking a drawer's end-of-turn reveal
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, async_graphql::Enum, PartialEq, Eq)]
+pub enum AuditStatus {
+    /// Turn is running or the drawer has not revealed the word yet
+    Pending,
+    /// Revealed word matches the commitment and every scored guess
+    Verified,
+    /// Revealed word and salt do not hash to the published commitment
+    CommitmentMismatch,
+    /// A guess scored as wrong actually matches the revealed word, or one scored as correct doesn't
+    ScoringMismatch,
+}
+
+// How the word for a turn was settled
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, async_graphql::Enum, PartialEq, Eq)]
+pub enum TurnOutcome {
+    /// Drawer picked the word
+    Played,
+    /// Drawer's chain picked a candidate after the selection window expired
+    AutoPicked,
+    /// Drawer never picked a word and the turn was skipped
+    Skipped,
+}
+
+impl Default for TurnOutcome {
+    fn default() -> Self {
+        TurnOutcome::Played
+    }
+}
+
+// Per-turn record used to audit the drawer once the word is revealed
+#[derive(Debug, Clone, Serialize, Deserialize, async_graphql::SimpleObject)]
+#[graphql(rename_fields = "camelCase")]
+pub struct TurnAudit {
+    pub round: u32,
+    pub drawer_chain_id: ChainId,
+    pub commitment: String,
+    pub revealed_word: Option<String>,
+    pub wrong_guesses: Vec<String>,
+    pub correct_guessers: Vec<ChainId>,
+    pub status: AuditStatus,
+    #[serde(default)]
+    pub outcome: TurnOutcome,
+    #[serde(default)]
+    pub guess_validation: GuessValidation, // Drawer: the drawer scored the guesses, so its scoring is audited
+    #[serde(default)]
+    #[graphql(skip)]
+    pub guess_commitments: Vec<String>, // `ChatMessage::guess_commitment` of each correct guess, in order
+    #[serde(default)]
+    pub correct_guesses: Vec<String>, // Text of the correct guesses, published with the reveal
+}
+
+impl TurnAudit {
+    pub fn new(round: u32, drawer_chain_id: ChainId, commitment: String, outcome: TurnOutcome, guess_validation: GuessValidation) -> Self {
+        Self {
+            round,
+            drawer_chain_id,
+            commitment,
+            revealed_word: None,
+            wrong_guesses: Vec::new(),
+            correct_guessers: Vec::new(),
+            status: AuditStatus::Pending,
+            outcome,
+            guess_validation,
+            guess_commitments: Vec::new(),
+            correct_guesses: Vec::new(),
+        }
+    }
+
+    /// Record of a turn where no word was chosen (nothing to audit)
+    pub fn skipped(round: u32, drawer_chain_id: ChainId) -> Self {
+        Self {
+            status: AuditStatus::Verified,
+            ..Self::new(round, drawer_chain_id, String::new(), TurnOutcome::Skipped, GuessValidation::default())
+        }
+    }
+
+    /// Record how the drawer scored a guess during this turn
+    pub fn record(&mut self, message: &ChatMessage) {
+        if message.is_correct_guess {
+            self.correct_guessers.push(message.player_chain_id);
+            // A drawer-scored guess sent without a commitment can't be checked, so it fails the audit
+            self.guess_commitments.push(message.guess_commitment.clone().unwrap_or_default());
+        } else {
+            self.wrong_guesses.push(message.message.clone());
+        }
+    }
+
+    /// Check the revealed word against the commitment and the recorded scoring
+    /// `correct_guesses` are the texts behind the commitments of the correct guesses, in order
+    pub fn verify(&mut self, word: &str, salt: &str, correct_guesses: &[String]) -> AuditStatus {
+        self.revealed_word = Some(word.to_string());
+        self.correct_guesses = correct_guesses.to_vec();
+        self.status = if word_commitment(word, salt) != self.commitment {
+            AuditStatus::CommitmentMismatch
+        } else if self.wrong_guesses.iter().any(|guess| guess::check_guess(word, guess) == guess::GuessVerdict::Correct)
+            || (self.guess_validation == GuessValidation::Drawer && !self.correct_guesses_match(word, salt))
+        {
+            AuditStatus::ScoringMismatch
+        } else {
+            AuditStatus::Verified
+        };
+        self.status
+    }
+
+    /// Every correct guess opens its commitment and really matches the word
+    fn correct_guesses_match(&self, word: &str, salt: &str) -> bool {
+        self.correct_guesses.len() == self.guess_commitments.len()
+            && self.correct_guesses.iter().zip(&self.guess_commitments).all(|(guess, commitment)| {
+                word_commitment(guess, salt) == *commitment && guess::check_guess(word, guess) == guess::GuessVerdict::Correct
+            })
+    }
+}
+
+// Hash preimage of a room password (salted with the room ID so equal passwords hash differently)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PasswordPreimage {
+    room_id: String,
+    password: String,
+}
+
+impl BcsHashable<'_> for PasswordPreimage {}
+
+/// Hash stored in `RoomSettings::password_hash`; the host hashes the plaintext sent in operations and join requests
+pub fn password_hash(room_id: &str, password: &str) -> String {
+    CryptoHash::new(&PasswordPreimage {
+        room_id: room_id.to_string(),
+        password: password.to_string(),
+    })
+    .to_string()
+}
+
+// Hash preimage of a word commitment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct WordCommitmentPreimage {
+    word: String,
+    salt: String,
+}
+
+impl BcsHashable<'_> for WordCommitmentPreimage {}
+
+/// Normalize a word or guess before comparing or hashing it
+/// ("Ice-Cream" and "ice cream" normalize the same way)
+pub fn normalize_word(word: &str) -> String {
+    guess::match_key(word)
+}
+
+/// Salted hash published in `WordChosen` and checked against `WordRevealed`
+pub fn word_commitment(word: &str, salt: &str) -> String {
+    CryptoHash::new(&WordCommitmentPreimage {
+        word: normalize_word(word),
+        salt: salt.to_string(),
+    })
+    .to_string()
 }
 
 // Archived room data (stored after deletion)
@@ -83,7 +520,17 @@ pub struct GameRoom {
 pub struct ArchivedRoom {
     pub room_id: String,
     pub blob_hashes: Vec<String>,
-    pub timestamp: String,
+    pub timestamp: Timestamp,
+}
+
+// Stream events dropped because their source chain may not send them (diagnostics)
+#[derive(Debug, Clone, Serialize, Deserialize, async_graphql::SimpleObject)]
+#[graphql(rename_fields = "camelCase")]
+pub struct RejectedEvents {
+    pub event: String,
+    pub source_chain_id: ChainId,
+    pub count: u64,
+    pub last_rejected_at: Timestamp,
 }
 
 // Game states
@@ -98,14 +545,52 @@ pub enum GameState {
     GameEnded,
 }
 
+// Time the drawer has to pick a word once chosen
+pub const DEFAULT_WORD_SELECTION_SECONDS: u32 = 20;
+pub const MIN_WORD_SELECTION_SECONDS: u32 = 5;
+pub const MAX_WORD_SELECTION_SECONDS: u32 = 120;
+
+// Room capacity, counting the host
+pub const DEFAULT_MAX_PLAYERS: u32 = 8;
+pub const MIN_PLAYERS: u32 = 2;
+pub const MAX_PLAYERS: u32 = 16;
+
+// How long a join request waits for the host's approval before it is turned away
+pub const JOIN_APPROVAL_TIMEOUT_SECONDS: u64 = 300;
+
+// Why the host moved on to the next turn
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, async_graphql::Enum, PartialEq, Eq)]
+pub enum TurnEndReason {
+    /// Host called ChooseDrawer
+    HostAdvanced,
+    /// Drawing time (seconds_per_round) ran out
+    DrawTimeout,
+    /// Drawer did not pick a word in time
+    WordSelectionTimeout,
+    /// Drawer left or was kicked mid-turn
+    DrawerLeft,
+    /// Every active guesser found the word
+    AllGuessed,
+}
+
 // Chat message
 #[derive(Debug, Clone, Serialize, Deserialize, async_graphql::SimpleObject)]
 #[graphql(rename_fields = "camelCase")]
 pub struct ChatMessage {
+    pub player_chain_id: ChainId, // Sender identity; the name is only for display
     pub player_name: String,
     pub message: String,
     pub is_correct_guess: bool,
     pub points_awarded: u32,
+    #[serde(default)]
+    pub is_close: bool, // Only ever set on the guesser's own chain
+    #[serde(default)]
+    pub elapsed_ms: u64, // Time since the word was chosen, per the scoring chain's block time
+    #[serde(default)]
+    pub sequence: u64, // Numbered from 1 each turn by the scoring chain (GameRoom::next_chat_sequence)
+    #[serde(default)]
+    #[graphql(skip)]
+    pub guess_commitment: Option<String>, // Correct guesses scored by the drawer: the guess hashed with the word's salt, opened at reveal
 }
 
 // Game event for history tracking (deprecated - no longer used)
@@ -127,26 +612,127 @@ pub struct DataBlobInfo {
     pub size: u32,
 }
 
+// Why a host refused a join request
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, async_graphql::Enum, PartialEq, Eq)]
+pub enum JoinRejectReason {
+    /// The host has no room, or not the one the invitation was for
+    NoRoom,
+    RoomFull,
+    /// The room is private and the host did not invite this chain
+    Private,
+    WrongPassword,
+    Banned,
+    /// A game is running and the applicant wasn't in the room before it started
+    GameStarted,
+    /// The host turned the request down (approval-required rooms)
+    NotApproved,
+    /// The host didn't approve the request in time
+    Expired,
+    /// Any other refusal (see the host's logs)
+    Refused,
+}
+
+impl JoinRejectReason {
+    pub fn from_error(error: &DoodleError) -> Self {
+        match error {
+            DoodleError::NoActiveRoom | DoodleError::NotHost | DoodleError::WrongRoom(_) => JoinRejectReason::NoRoom,
+            DoodleError::RoomFull => JoinRejectReason::RoomFull,
+            DoodleError::NotInvited => JoinRejectReason::Private,
+            DoodleError::WrongPassword => JoinRejectReason::WrongPassword,
+            DoodleError::Banned => JoinRejectReason::Banned,
+            DoodleError::GameAlreadyStarted => JoinRejectReason::GameStarted,
+            _ => JoinRejectReason::Refused,
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            JoinRejectReason::NoRoom => "no room",
+            JoinRejectReason::RoomFull => "room full",
+            JoinRejectReason::Private => "room is private",
+            JoinRejectReason::WrongPassword => "wrong password",
+            JoinRejectReason::Banned => "banned",
+            JoinRejectReason::GameStarted => "game already started",
+            JoinRejectReason::NotApproved => "not approved by host",
+            JoinRejectReason::Expired => "host didn't answer in time",
+            JoinRejectReason::Refused => "refused by host",
+        }
+    }
+}
+
+// Join request this chain sent and is waiting on (player side)
+#[derive(Debug, Clone, Serialize, Deserialize, async_graphql::SimpleObject)]
+#[graphql(rename_fields = "camelCase")]
+pub struct PendingJoin {
+    pub host_chain_id: ChainId,
+    pub room_id: Option<String>,
+    pub requested_at: Timestamp,
+    pub rejection: Option<JoinRejectReason>, // Set when the host answered with JoinRejected
+}
+
+impl PendingJoin {
+    /// "joining…" or "rejected: <reason>"
+    pub fn status(&self) -> String {
+        match self.rejection {
+            Some(reason) => format!("rejected: {}", reason.description()),
+            None => "joining…".to_string(),
+        }
+    }
+}
+
+// Join request waiting for the host's approval (host side, approval-required rooms)
+#[derive(Debug, Clone, Serialize, Deserialize, async_graphql::SimpleObject)]
+#[graphql(rename_fields = "camelCase")]
+pub struct JoinApplication {
+    pub chain_id: ChainId,
+    pub player_name: String,
+    pub avatar_json: String,
+    pub requested_at: Timestamp,
+}
+
+impl JoinApplication {
+    pub fn is_expired(&self, now: Timestamp) -> bool {
+        now >= self.requested_at.saturating_add(TimeDelta::from_secs(JOIN_APPROVAL_TIMEOUT_SECONDS))
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, async_graphql::SimpleObject)]
 #[graphql(rename_fields = "camelCase")]
 pub struct Invitation {
-    pub host_chain_id: String,
-    pub timestamp: String,
+    pub host_chain_id: ChainId,
+    pub timestamp: Timestamp,
+    #[serde(default)]
+    pub room_id: String,
 }
 
 // Operations
 #[derive(Debug, Serialize, Deserialize)]
 pub enum Operation {
-    CreateRoom { host_name: String, avatar_json: String },
-    JoinRoom { host_chain_id: String, player_name: String, avatar_json: String },
+    // Passwords travel in plaintext (visible in the block) and only their hash is stored
+    CreateRoom { host_name: String, avatar_json: String, settings: RoomSettings, password: Option<String> },
+    JoinRoom { host_chain_id: String, player_name: String, avatar_json: String, password: Option<String> },
+    // Host only, in the lobby: change the room settings; `password` None keeps the current one, empty removes it
+    UpdateSettings { settings: RoomSettings, password: Option<String> },
     StartGame { rounds: u32, seconds_per_round: u32 },
     ChooseDrawer, // logic moved to async, no hash needed here
-    ChooseWord { word: String },
+    Tick, // Anyone: enforce turn deadlines (sent on to the host from other chains)
+    ChooseWord { word: String, salt: String },
+    // Drawer only: secret salt kept for the word the drawer's chain picks if the selection window expires
+    ReserveWordSalt { salt: String },
+    PublishHint, // Drawer only: broadcast the letter hint due at the current time
     GuessWord { guess: String },
     EndMatch,
     LeaveRoom { blob_hashes: Option<Vec<String>> },
+    // Host only: remove a player from the room; a ban also refuses their future join requests
+    KickPlayer { player_chain_id: String },
+    BanPlayer { player_chain_id: String },
+    // Host only, approval-required rooms: answer a queued join request
+    ApproveJoin { player_chain_id: String },
+    RejectJoin { player_chain_id: String },
     // Data Blob operations (read only - blobs created via CLI/GraphQL)
     ReadDataBlob { hash: String },
+    // Validate a custom word pack blob and remember it for room settings
+    AddWordPack { hash: String },
     
     // Friend System
     RequestFriend { target_chain_id: String },
@@ -157,87 +743,353 @@ pub enum Operation {
     InviteFriend { friend_chain_id: String },
     AcceptInvite { host_chain_id: String, player_name: String, avatar_json: String },
     DeclineInvite { host_chain_id: String },
+    
+    // Quick Play (matchmaker on the registry chain)
+    QueueForMatch { player_name: String, avatar_json: String, preferences: registry::MatchPreferences },
+    LeaveMatchQueue,
+}
+
+impl Operation {
+    /// Checks that only need this chain's room state and the drawer's word candidates:
+    /// the caller's role (host, current drawer, active player), the game state and argument formats
+    /// Run by the contract before executing and by the service before scheduling
+    pub fn precheck(&self, room: Option<&GameRoom>, chain_id: ChainId, word_candidates: &[String]) -> Result<(), DoodleError> {
+        let room_ref = || room.ok_or(DoodleError::NoActiveRoom);
+        match self {
+            Operation::CreateRoom { settings, .. } => settings.check_capacity(1),
+            Operation::JoinRoom { host_chain_id, .. } | Operation::AcceptInvite { host_chain_id, .. } => {
+                parse_chain_id(host_chain_id).map(|_| ())
+            }
+            Operation::Tick | Operation::LeaveRoom { .. } => room_ref().map(|_| ()),
+            
+            // Host only
+            Operation::StartGame { .. } | Operation::ChooseDrawer | Operation::EndMatch => room_ref()?.ensure_host(chain_id),
+            Operation::UpdateSettings { settings, .. } => {
+                let room = room_ref()?;
+                room.ensure_host(chain_id)?;
+                room.ensure_lobby()?;
+                settings.check_capacity(room.active_player_count())
+            }
+            Operation::InviteFriend { friend_chain_id } => {
+                room_ref()?.ensure_host(chain_id)?;
+                parse_chain_id(friend_chain_id).map(|_| ())
+            }
+            Operation::ApproveJoin { player_chain_id } | Operation::RejectJoin { player_chain_id } => {
+                room_ref()?.ensure_host(chain_id)?;
+                parse_chain_id(player_chain_id).map(|_| ())
+            }
+            Operation::KickPlayer { player_chain_id } => {
+                let room = room_ref()?;
+                room.ensure_host(chain_id)?;
+                room.ensure_kickable(parse_chain_id(player_chain_id)?)
+            }
+            Operation::BanPlayer { player_chain_id } => {
+                let room = room_ref()?;
+                room.ensure_host(chain_id)?;
+                // Chains that are not (or no longer) in the room can be banned too
+                match room.ensure_kickable(parse_chain_id(player_chain_id)?) {
+                    Err(DoodleError::UnknownPlayer(_)) => Ok(()),
+                    result => result,
+                }
+            }
+            
+            // Current drawer only
+            Operation::ChooseWord { word, salt } => {
+                let room = room_ref()?;
+                room.ensure_drawer(chain_id)?;
+                room.ensure_state(GameState::WaitingForWord)?;
+                if salt.is_empty() {
+                    return Err(DoodleError::MissingSalt);
+                }
+                offered_word(word_candidates, word).map(|_| ())
+            }
+            Operation::ReserveWordSalt { salt } => {
+                let room = room_ref()?;
+                room.ensure_drawer(chain_id)?;
+                room.ensure_state(GameState::WaitingForWord)?;
+                if salt.is_empty() {
+                    return Err(DoodleError::MissingSalt);
+                }
+                Ok(())
+            }
+            Operation::PublishHint => {
+                let room = room_ref()?;
+                room.ensure_drawer(chain_id)?;
+                room.ensure_state(GameState::Drawing)
+            }
+            
+            // Active players other than the drawer
+            Operation::GuessWord { .. } => {
+                let room = room_ref()?;
+                room.ensure_guesser(chain_id)?;
+                room.ensure_state(GameState::Drawing)
+            }
+            
+            Operation::ReadDataBlob { hash } | Operation::AddWordPack { hash } => {
+                CryptoHash::from_str(hash)
+                    .map(|_| ())
+                    .map_err(|_| DoodleError::InvalidBlobHash(hash.clone()))
+            }
+            Operation::RequestFriend { target_chain_id: id }
+            | Operation::AcceptFriend { requester_chain_id: id } => parse_chain_id(id).map(|_| ()),
+            Operation::DeclineFriend { .. } | Operation::DeclineInvite { .. } | Operation::LeaveMatchQueue => Ok(()),
+            Operation::QueueForMatch { .. } => match room {
+                Some(_) => Err(DoodleError::AlreadyInRoom),
+                None => Ok(()),
+            },
+        }
+    }
+}
+
+pub fn parse_chain_id(id: &str) -> Result<ChainId, DoodleError> {
+    id.parse().map_err(|_| DoodleError::InvalidChainId(id.to_string()))
+}
+
+/// The offered candidate matching `word` (in the bank's spelling)
+pub fn offered_word(candidates: &[String], word: &str) -> Result<String, DoodleError> {
+    candidates.iter()
+        .find(|candidate| normalize_word(candidate) == normalize_word(word))
+        .cloned()
+        .ok_or_else(|| DoodleError::WordNotOffered(word.to_string()))
 }
 
 // Events for cross-chain synchronization
+// Every event names the room it belongs to; replicas drop events for any other room
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum DoodleEvent {
-    PlayerJoined { player: Player, timestamp: String },
-    PlayerLeft { player_chain_id: String, timestamp: String },
+    PlayerJoined { room_id: String, player: Player, timestamp: Timestamp },
+    PlayerLeft { room_id: String, player_chain_id: ChainId, timestamp: Timestamp },
+    // Host removed a player (and, if banned, refuses their future join requests)
+    PlayerKicked { room_id: String, player_chain_id: ChainId, banned: bool, timestamp: Timestamp },
+    // Host changed the room settings in the lobby
+    SettingsChanged { room_id: String, settings: RoomSettings, timestamp: Timestamp },
     GameStarted { 
+        room_id: String,
         rounds: u32, 
         seconds_per_round: u32, 
+        settings: RoomSettings,
         drawer_index: usize, 
         drawer_name: String, 
-        timestamp: String 
+        timestamp: Timestamp 
     },
     DrawerChosen { 
+        room_id: String,
         drawer_index: usize, 
         drawer_name: String, 
-        timestamp: String,
-        previous_blob_hash: Option<String> 
+        timestamp: Timestamp,
+        previous_blob_hash: Option<String>,
+        reason: TurnEndReason,
+    },
+    WordChosen {
+        room_id: String,
+        commitment: String,
+        timestamp: Timestamp,
+        #[serde(default)]
+        outcome: TurnOutcome,
     },
-    WordChosen { timestamp: String },
-    ChatMessage { message: ChatMessage },
+    // Drawer reports that every active guesser has found the word
+    TurnComplete { room_id: String, round: u32, timestamp: Timestamp },
+    // Host asked the drawer's chain to pick a word once the selection window expired (AutoPick rooms)
+    AutoPickRequested { room_id: String, drawer_index: usize, timestamp: Timestamp },
+    // Host skipped a drawer who didn't pick a word in time
+    TurnSkipped { room_id: String, drawer_index: usize, drawer_name: String, timestamp: Timestamp },
+    // Emitted by the drawer when its turn ends so every chain can audit the commitment
+    // `correct_guesses` opens the guess commitments of drawer-scored rooms (the texts stay off-chain until now)
+    WordRevealed { room_id: String, commitment: String, word: String, salt: String, correct_guesses: Vec<String>, timestamp: Timestamp },
+    ChatMessage { room_id: String, message: ChatMessage },
+    HintRevealed { room_id: String, hint: WordHint },
     RoundEnded { 
-        scores: Vec<(String, u32)>, 
-        timestamp: String 
+        room_id: String,
+        scores: Vec<(ChainId, u32)>,
+        reason: TurnEndReason,
+        timestamp: Timestamp 
     },
-    GameEnded { final_scores: Vec<(String, u32)>, timestamp: String },
-    MatchEnded { timestamp: String },
+    GameEnded { room_id: String, final_scores: Vec<(ChainId, u32)>, reason: TurnEndReason, timestamp: Timestamp },
+    MatchEnded { room_id: String, timestamp: Timestamp },
+}
+
+impl DoodleEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            DoodleEvent::PlayerJoined { .. } => "PlayerJoined",
+            DoodleEvent::PlayerLeft { .. } => "PlayerLeft",
+            DoodleEvent::PlayerKicked { .. } => "PlayerKicked",
+            DoodleEvent::SettingsChanged { .. } => "SettingsChanged",
+            DoodleEvent::GameStarted { .. } => "GameStarted",
+            DoodleEvent::DrawerChosen { .. } => "DrawerChosen",
+            DoodleEvent::WordChosen { .. } => "WordChosen",
+            DoodleEvent::TurnComplete { .. } => "TurnComplete",
+            DoodleEvent::AutoPickRequested { .. } => "AutoPickRequested",
+            DoodleEvent::TurnSkipped { .. } => "TurnSkipped",
+            DoodleEvent::WordRevealed { .. } => "WordRevealed",
+            DoodleEvent::ChatMessage { .. } => "ChatMessage",
+            DoodleEvent::HintRevealed { .. } => "HintRevealed",
+            DoodleEvent::RoundEnded { .. } => "RoundEnded",
+            DoodleEvent::GameEnded { .. } => "GameEnded",
+            DoodleEvent::MatchEnded { .. } => "MatchEnded",
+        }
+    }
+
+    pub fn room_id(&self) -> &str {
+        match self {
+            DoodleEvent::PlayerJoined { room_id, .. }
+            | DoodleEvent::PlayerLeft { room_id, .. }
+            | DoodleEvent::PlayerKicked { room_id, .. }
+            | DoodleEvent::SettingsChanged { room_id, .. }
+            | DoodleEvent::GameStarted { room_id, .. }
+            | DoodleEvent::DrawerChosen { room_id, .. }
+            | DoodleEvent::WordChosen { room_id, .. }
+            | DoodleEvent::TurnComplete { room_id, .. }
+            | DoodleEvent::AutoPickRequested { room_id, .. }
+            | DoodleEvent::TurnSkipped { room_id, .. }
+            | DoodleEvent::WordRevealed { room_id, .. }
+            | DoodleEvent::ChatMessage { room_id, .. }
+            | DoodleEvent::HintRevealed { room_id, .. }
+            | DoodleEvent::RoundEnded { room_id, .. }
+            | DoodleEvent::GameEnded { room_id, .. }
+            | DoodleEvent::MatchEnded { room_id, .. } => room_id,
+        }
+    }
 }
 
 // Cross-chain messages
 #[derive(Debug, Clone, Serialize, Deserialize)]
+// Senders are identified by the runtime's message origin, never by the payload
+// Room messages name their room so a chain that has moved on can drop them
 pub enum CrossChainMessage {
     JoinRequest { 
-        player_chain_id: linera_sdk::linera_base_types::ChainId,
+        room_id: Option<String>, // Set when joining through an invitation to a specific room
         player_name: String,
         avatar_json: String,
+        password: Option<String>,
     },
     GuessSubmission {
-        guesser_chain_id: linera_sdk::linera_base_types::ChainId,
+        room_id: String,
         guess: String,
         round: u32,
     },
+    // Ask the host to check turn deadlines
+    Tick { room_id: String },
+    // Host asks the drawer's chain to pick a word after the selection window expired
+    AutoPickWord { room_id: String, round: u32 },
+    // Drawer gives the host what it needs to score guesses (host-validated rooms)
+    HostGuessCheck {
+        room_id: String,
+        check: GuessCheck,
+    },
+    // Private "you're close" feedback from the scoring chain to the guesser
+    CloseGuess {
+        room_id: String,
+        round: u32,
+        guess: String,
+    },
     // Initial state sync when player joins (one-time)
     InitialStateSync {
         room_data: GameRoom,
     },
+    // Host refused a JoinRequest
+    JoinRejected {
+        reason: JoinRejectReason,
+    },
     // Повідомлення про видалення кімнати (відключення всіх гравців)
     RoomDeleted {
-        timestamp: String,
+        room_id: String,
+        timestamp: Timestamp,
         archived_room: Option<ArchivedRoom>,
     },
     PlayerLeft {
-        player_chain_id: linera_sdk::linera_base_types::ChainId,
+        room_id: String,
         player_name: Option<String>,
-        timestamp: String,
+        timestamp: Timestamp,
+    },
+    // Host removed this chain's player from the room
+    Kicked {
+        room_id: String,
+        banned: bool,
     },
     
     // Friend System Messages
-    FriendRequest {
-        requester_chain_id: linera_sdk::linera_base_types::ChainId,
-    },
-    FriendAccepted {
-        target_chain_id: linera_sdk::linera_base_types::ChainId,
-    },
+    FriendRequest,
+    FriendAccepted,
     
     // Invite System Messages
     RoomInvitation {
-        host_chain_id: linera_sdk::linera_base_types::ChainId,
-        timestamp: String,
+        room_id: String,
+        timestamp: Timestamp,
+    },
+    RoomInvitationCancelled { room_id: String },
+
+    // Public room directory (host to registry chain)
+    RegisterRoom { listing: registry::RoomListing },
+    RoomHeartbeat { listing: registry::RoomListing },
+    UnregisterRoom { room_id: String },
+
+    // Quick Play: player to matchmaker
+    QueueForMatch {
+        player_name: String,
+        avatar_json: String,
+        preferences: registry::MatchPreferences,
     },
-    RoomInvitationCancelled {
-        host_chain_id: linera_sdk::linera_base_types::ChainId,
+    LeaveMatchQueue,
+    // Matchmaker picked this chain to host a room for the other `players`
+    HostMatch {
+        settings: RoomSettings,
+        rounds: u32,
+        players: Vec<ChainId>,
     },
+    // Matchmaker (or the match host) tells a queued player which room to send its JoinRequest to
+    JoinMatch {
+        host_chain_id: ChainId,
+        room_id: String,
+    },
+    // Queued player was dropped without a match
+    MatchCancelled,
 }
 
-impl GameRoom {
-    pub fn new(host_chain_id: String, host_name: String, avatar_json: String, _timestamp: String) -> Self {
-        let host_player = Player {
-            chain_id: host_chain_id.clone(),
-            name: host_name.clone(),
+impl CrossChainMessage {
+    /// Room the receiving chain must currently be in for this message to apply
+    /// None for messages that don't need a room (joining, invitations, friends, room directory, matchmaking);
+    /// a JoinRequest for another room is answered with JoinRejected instead
+    pub fn target_room(&self) -> Option<&str> {
+        match self {
+            CrossChainMessage::GuessSubmission { room_id, .. }
+            | CrossChainMessage::Tick { room_id }
+            | CrossChainMessage::AutoPickWord { room_id, .. }
+            | CrossChainMessage::HostGuessCheck { room_id, .. }
+            | CrossChainMessage::CloseGuess { room_id, .. }
+            | CrossChainMessage::RoomDeleted { room_id, .. }
+            | CrossChainMessage::PlayerLeft { room_id, .. }
+            | CrossChainMessage::Kicked { room_id, .. } => Some(room_id),
+            CrossChainMessage::JoinRequest { .. }
+            | CrossChainMessage::InitialStateSync { .. }
+            | CrossChainMessage::JoinRejected { .. }
+            | CrossChainMessage::FriendRequest
+            | CrossChainMessage::FriendAccepted
+            | CrossChainMessage::RoomInvitation { .. }
+            | CrossChainMessage::RoomInvitationCancelled { .. }
+            | CrossChainMessage::RegisterRoom { .. }
+            | CrossChainMessage::RoomHeartbeat { .. }
+            | CrossChainMessage::UnregisterRoom { .. }
+            | CrossChainMessage::QueueForMatch { .. }
+            | CrossChainMessage::LeaveMatchQueue
+            | CrossChainMessage::HostMatch { .. }
+            | CrossChainMessage::JoinMatch { .. }
+            | CrossChainMessage::MatchCancelled => None,
+        }
+    }
+}
+
+/// Room ID unique across chains and across rooms of one chain:
+/// host chain, the host's room counter and the creation time
+pub fn new_room_id(host_chain_id: ChainId, room_number: u64, timestamp: Timestamp) -> String {
+    format!("{}:{}:{}", host_chain_id, room_number, timestamp.micros())
+}
+
+impl GameRoom {
+    pub fn new(host_chain_id: ChainId, room_number: u64, host_name: String, avatar_json: String, settings: RoomSettings, timestamp: Timestamp) -> Self {
+        let host_player = Player {
+            chain_id: host_chain_id,
+            name: host_name.clone(),
             avatar_json,
             score: 0,
             has_guessed: false,
@@ -245,7 +1097,7 @@ impl GameRoom {
         };
         
         Self {
-            room_id: _timestamp.clone(),
+            room_id: new_room_id(host_chain_id, room_number, timestamp),
             host_chain_id,
             players: vec![host_player],
             game_state: GameState::WaitingForPlayers,
@@ -257,10 +1109,18 @@ impl GameRoom {
             chat_messages: Vec::new(),
             drawer_chosen_at: None,
             blob_hashes: Vec::new(),
+            settings,
+            word_hint: None,
+            turn_guess_millis: Vec::new(),
+            chat_sequence: 0,
+            current_turn: None,
+            turn_audits: Vec::new(),
+            auto_pick_requested_at: None,
+            banned: Vec::new(),
         }
     }
 
-    pub fn add_player(&mut self, player: Player) {
+    fn add_player(&mut self, player: Player) {
         if let Some(existing) = self.players.iter_mut().find(|p| p.chain_id == player.chain_id) {
             existing.name = player.name;
             existing.avatar_json = player.avatar_json;
@@ -270,11 +1130,14 @@ impl GameRoom {
         }
     }
     
-    pub fn start_game(&mut self, rounds: u32, seconds_per_round: u32, _timestamp: String) {
+    fn start_game(&mut self, rounds: u32, seconds_per_round: u32, settings: RoomSettings) {
         self.total_rounds = rounds;
         self.seconds_per_round = seconds_per_round;
+        self.settings = settings;
         self.current_round = 1;
         self.game_state = GameState::ChoosingDrawer;
+        self.current_turn = None;
+        self.turn_audits.clear();
         
         // Reset all player scores and guessed status
         for player in &mut self.players {
@@ -283,7 +1146,8 @@ impl GameRoom {
         }
     }
 
-    pub fn choose_drawer(&mut self, timestamp: String) -> Option<usize> {
+    /// Index of the next active player to draw after the current drawer
+    pub fn next_drawer_index(&self) -> Option<usize> {
         if self.players.iter().all(|p| p.status != PlayerStatus::Active) {
             return None;
         }
@@ -303,31 +1167,355 @@ impl GameRoom {
             }
         }
 
-        let next_index = next_index?;
+        next_index
+    }
 
-        self.current_drawer_index = Some(next_index);
+    fn set_drawer(&mut self, drawer_index: usize, timestamp: Timestamp) {
+        self.close_turn();
+        self.current_drawer_index = Some(drawer_index);
         self.game_state = GameState::WaitingForWord;
         self.word_chosen_at = None;
-        self.drawer_chosen_at = Some(timestamp.clone());
+        self.drawer_chosen_at = Some(timestamp);
+        self.auto_pick_requested_at = None;
         
         // Reset guessed status for new drawer
         for player in &mut self.players {
             player.has_guessed = false;
         }
-
-        Some(next_index)
     }
 
-    pub fn choose_word(&mut self, timestamp: String) {
+    fn choose_word(&mut self, commitment: String, outcome: TurnOutcome, timestamp: Timestamp) {
         self.word_chosen_at = Some(timestamp);
         self.game_state = GameState::Drawing;
+        self.word_hint = None;
+        self.auto_pick_requested_at = None;
+        self.turn_guess_millis.clear();
+        self.chat_sequence = 0;
+        self.current_turn = self.get_current_drawer()
+            .map(|drawer| TurnAudit::new(self.current_round, drawer.chain_id, commitment, outcome, self.settings.guess_validation));
+    }
+
+    /// Record that the current drawer never picked a word (the host then advances the turn)
+    fn skip_turn(&mut self) {
+        self.current_turn = None;
+        self.auto_pick_requested_at = None;
+        if let Some(drawer_chain_id) = self.get_current_drawer().map(|drawer| drawer.chain_id) {
+            self.push_turn_audit(TurnAudit::skipped(self.current_round, drawer_chain_id));
+        }
+    }
+
+    /// Word candidates offered to the current drawer for this turn
+    /// `nonce` comes from the drawer's chain so other players cannot predict the offer
+    pub fn word_candidates(&self, bank: &WordBank, nonce: &str) -> Vec<String> {
+        let seed = format!("{}:{}:{:?}:{}", self.room_id, self.current_round, self.current_drawer_index, nonce);
+        bank.pick_candidates(
+            self.settings.word_category.as_deref(),
+            self.settings.word_difficulty,
+            self.settings.word_choices(),
+            &seed,
+        )
+    }
+
+    /// Sequence for the next chat message this (scoring) chain publishes
+    pub fn next_chat_sequence(&self) -> u64 {
+        self.chat_sequence + 1
     }
 
-    pub fn add_chat_message(&mut self, message: ChatMessage) {
+    fn add_chat_message(&mut self, message: ChatMessage) {
+        if let Some(turn) = self.current_turn.as_mut() {
+            turn.record(&message);
+        }
+        if message.is_correct_guess {
+            self.turn_guess_millis.push(message.elapsed_ms);
+        }
         self.chat_messages.push(message);
     }
 
-    pub fn advance_to_next_round(&mut self, _timestamp: String) {
+    /// Flag a player's own wrong guesses that the scorer reported as close
+    pub fn mark_close_guesses(&mut self, player_chain_id: ChainId, close_guesses: &[String]) {
+        for message in &mut self.chat_messages {
+            if message.player_chain_id == player_chain_id && !message.is_correct_guess && close_guesses.contains(&message.message) {
+                message.is_close = true;
+            }
+        }
+    }
+
+    /// End the running turn: score the drawer and move the turn into the audit history
+    /// Must run before `current_drawer_index` moves on
+    fn close_turn(&mut self) {
+        self.word_hint = None;
+        if let Some(turn) = self.current_turn.take() {
+            self.award_drawer_points();
+            self.push_turn_audit(turn);
+        }
+        self.turn_guess_millis.clear();
+    }
+
+    fn push_turn_audit(&mut self, turn: TurnAudit) {
+        self.turn_audits.push(turn);
+        if self.turn_audits.len() > MAX_TURN_AUDITS {
+            self.turn_audits.remove(0);
+        }
+    }
+
+    fn award_drawer_points(&mut self) {
+        let Some(drawer_index) = self.current_drawer_index else {
+            return;
+        };
+        let eligible = self.players.iter()
+            .enumerate()
+            .filter(|(index, p)| *index != drawer_index && p.status == PlayerStatus::Active)
+            .count() as u32;
+        let delta = self.settings.scoring().drawer_points(&self.turn_guess_millis, eligible, self.seconds_per_round);
+
+        if let Some(drawer) = self.players.get_mut(drawer_index) {
+            if drawer.status == PlayerStatus::Active {
+                drawer.score = if delta >= 0 {
+                    drawer.score.saturating_add(delta as u32)
+                } else {
+                    drawer.score.saturating_sub(delta.unsigned_abs() as u32)
+                };
+            }
+        }
+    }
+
+    /// Apply a drawer's reveal to the turn it committed to
+    /// Returns None if no pending turn carries this commitment
+    fn apply_word_reveal(&mut self, commitment: &str, word: &str, salt: &str, correct_guesses: &[String]) -> Option<AuditStatus> {
+        self.turn_audits.iter_mut()
+            .chain(self.current_turn.iter_mut())
+            .find(|turn| turn.commitment == commitment && turn.status == AuditStatus::Pending)
+            .map(|turn| turn.verify(word, salt, correct_guesses))
+    }
+
+    /// Scores by chain ID as they will stand once the current turn is closed (drawer points included)
+    pub fn closing_scores(&self) -> Vec<(ChainId, u32)> {
+        let mut closed = self.clone();
+        closed.close_turn();
+        closed.players.iter()
+            .map(|p| (p.chain_id, p.score))
+            .collect()
+    }
+
+    /// Take the host's scores as authoritative, so a replica that missed or misapplied a guess converges
+    fn set_scores(&mut self, scores: &[(ChainId, u32)]) {
+        for (chain_id, score) in scores {
+            if let Some(player) = self.players.iter_mut().find(|p| p.chain_id == *chain_id) {
+                player.score = *score;
+            }
+        }
+    }
+
+    /// A game is running (between GameStarted and GameEnded)
+    pub fn is_in_progress(&self) -> bool {
+        matches!(
+            self.game_state,
+            GameState::ChoosingDrawer | GameState::WaitingForWord | GameState::Drawing | GameState::RoundEnded
+        )
+    }
+
+    /// Apply an event to the room: every game state transition goes through here
+    /// The host applies each event before emitting it and replicas apply the events they receive,
+    /// so all chains compute the same room. Illegal transitions return an error and change nothing
+    pub fn apply(&mut self, event: &DoodleEvent) -> Result<(), DoodleError> {
+        if event.room_id() != self.room_id {
+            return Err(DoodleError::WrongRoom(event.room_id().to_string()));
+        }
+        match event {
+            DoodleEvent::PlayerJoined { player, .. } => {
+                if self.is_banned(player.chain_id) {
+                    return Err(DoodleError::Banned);
+                }
+                self.ensure_room_for(player.chain_id)?;
+                // The host picks a unique name before publishing
+                if self.unique_name(player.chain_id, &player.name) != player.name {
+                    return Err(DoodleError::NameTaken(player.name.clone()));
+                }
+                // Also re-activates a returning player
+                self.add_player(player.clone());
+            }
+
+            DoodleEvent::PlayerLeft { player_chain_id, .. } => {
+                self.remove_player(*player_chain_id, PlayerStatus::Left)?;
+            }
+
+            DoodleEvent::PlayerKicked { player_chain_id, banned, .. } => {
+                match self.ensure_kickable(*player_chain_id) {
+                    Ok(()) => self.remove_player(*player_chain_id, PlayerStatus::Kicked)?,
+                    // A ban may target a chain that is not (or no longer) in the room
+                    Err(DoodleError::UnknownPlayer(_)) if *banned => {}
+                    Err(error) => return Err(error),
+                }
+                if *banned && !self.is_banned(*player_chain_id) {
+                    self.banned.push(*player_chain_id);
+                }
+            }
+
+            DoodleEvent::SettingsChanged { settings, .. } => {
+                if self.is_in_progress() {
+                    return Err(self.illegal(event));
+                }
+                self.settings = settings.clone();
+            }
+
+            DoodleEvent::GameStarted { rounds, seconds_per_round, settings, drawer_index, timestamp, .. } => {
+                self.require(event, &[GameState::WaitingForPlayers, GameState::GameEnded])?;
+                self.require_active_drawer(*drawer_index)?;
+                self.start_game(*rounds, *seconds_per_round, settings.clone());
+                self.set_drawer(*drawer_index, *timestamp);
+            }
+
+            DoodleEvent::DrawerChosen { drawer_index, timestamp, .. } => {
+                self.require(event, &[GameState::ChoosingDrawer, GameState::WaitingForWord, GameState::Drawing])?;
+                self.require_active_drawer(*drawer_index)?;
+                self.set_drawer(*drawer_index, *timestamp);
+            }
+
+            DoodleEvent::WordChosen { commitment, timestamp, outcome, .. } => {
+                self.require(event, &[GameState::WaitingForWord])?;
+                self.choose_word(commitment.clone(), *outcome, *timestamp);
+            }
+
+            DoodleEvent::TurnComplete { .. } => {
+                // Signal for the host only; it answers with DrawerChosen / RoundEnded / GameEnded
+                self.require(event, &[GameState::Drawing])?;
+            }
+
+            DoodleEvent::AutoPickRequested { drawer_index, timestamp, .. } => {
+                self.require(event, &[GameState::WaitingForWord])?;
+                if self.current_drawer_index != Some(*drawer_index) || self.auto_pick_requested_at.is_some() {
+                    return Err(self.illegal(event));
+                }
+                self.auto_pick_requested_at = Some(*timestamp);
+            }
+
+            DoodleEvent::TurnSkipped { drawer_index, .. } => {
+                self.require(event, &[GameState::WaitingForWord])?;
+                if self.current_drawer_index != Some(*drawer_index) {
+                    return Err(DoodleError::InvalidDrawer(*drawer_index));
+                }
+                self.skip_turn();
+            }
+
+            DoodleEvent::WordRevealed { commitment, word, salt, correct_guesses, .. } => {
+                self.apply_word_reveal(commitment, word, salt, correct_guesses)
+                    .ok_or_else(|| DoodleError::UnknownCommitment(commitment.clone()))?;
+            }
+
+            DoodleEvent::ChatMessage { message, .. } => {
+                self.require(event, &[GameState::Drawing])?;
+                // The scoring chain numbers its messages in order, so a number we have seen is a relayed copy
+                if message.sequence <= self.chat_sequence {
+                    return Ok(());
+                }
+                if message.is_correct_guess {
+                    let index = self.player_index(message.player_chain_id)?;
+                    if self.players[index].has_guessed {
+                        return Err(DoodleError::AlreadyGuessed(message.player_chain_id.to_string()));
+                    }
+                    let points = message.points_awarded.min(self.settings.scoring().max_points);
+                    self.players[index].has_guessed = true;
+                    self.players[index].score = self.players[index].score.saturating_add(points);
+                }
+                self.chat_sequence = message.sequence;
+
+                self.add_chat_message(message.clone());
+                // MEMORY OPTIMIZATION: Keep only last 10 chat messages to prevent overflow
+                if self.chat_messages.len() > 10 {
+                    self.chat_messages = self.chat_messages.split_off(self.chat_messages.len() - 10);
+                }
+            }
+
+            DoodleEvent::HintRevealed { hint, .. } => {
+                self.require(event, &[GameState::Drawing])?;
+                // Hints only ever reveal more letters
+                if self.word_hint.as_ref().map_or(false, |current| current.revealed_letters > hint.revealed_letters) {
+                    return Err(DoodleError::StaleHint);
+                }
+                self.word_hint = Some(hint.clone());
+            }
+
+            DoodleEvent::RoundEnded { scores, .. } => {
+                if !self.is_in_progress() || self.current_round >= self.total_rounds {
+                    return Err(self.illegal(event));
+                }
+                self.advance_to_next_round();
+                self.set_scores(scores);
+            }
+
+            DoodleEvent::GameEnded { final_scores, .. } => {
+                if !self.is_in_progress() {
+                    return Err(self.illegal(event));
+                }
+                self.close_turn();
+                self.set_scores(final_scores);
+                self.game_state = GameState::GameEnded;
+            }
+
+            DoodleEvent::MatchEnded { .. } => {
+                // Room is deleted via RoomDeleted messages
+            }
+        }
+        Ok(())
+    }
+
+    fn require(&self, event: &DoodleEvent, allowed: &[GameState]) -> Result<(), DoodleError> {
+        if allowed.contains(&self.game_state) {
+            Ok(())
+        } else {
+            Err(self.illegal(event))
+        }
+    }
+
+    fn illegal(&self, event: &DoodleEvent) -> DoodleError {
+        DoodleError::IllegalTransition { event: event.name().to_string(), state: self.game_state }
+    }
+
+    fn require_active_drawer(&self, drawer_index: usize) -> Result<(), DoodleError> {
+        match self.players.get(drawer_index) {
+            Some(player) if player.status == PlayerStatus::Active => Ok(()),
+            _ => Err(DoodleError::InvalidDrawer(drawer_index)),
+        }
+    }
+
+    /// `name`, or `name (2)`, `name (3)`, ... if another player in the room already uses it
+    pub fn unique_name(&self, chain_id: ChainId, name: &str) -> String {
+        let taken = |candidate: &str| self.players.iter()
+            .any(|p| p.chain_id != chain_id && p.name.eq_ignore_ascii_case(candidate));
+        let mut candidate = name.to_string();
+        let mut suffix = 2;
+        while taken(&candidate) {
+            candidate = format!("{} ({})", name, suffix);
+            suffix += 1;
+        }
+        candidate
+    }
+
+    /// Mark a player as gone; a drawer leaving mid-turn hands the turn back to the host
+    fn remove_player(&mut self, chain_id: ChainId, status: PlayerStatus) -> Result<(), DoodleError> {
+        let index = self.player_index(chain_id)?;
+        let player = &mut self.players[index];
+        player.status = status;
+        player.has_guessed = false;
+
+        // Keep the departed drawer's seat as the current index so the rotation carries on after it
+        if self.current_drawer_index == Some(index) && self.is_in_progress() {
+            self.close_turn();
+            self.game_state = GameState::ChoosingDrawer;
+            self.word_chosen_at = None;
+            self.drawer_chosen_at = None;
+        }
+        Ok(())
+    }
+
+    fn player_index(&self, chain_id: ChainId) -> Result<usize, DoodleError> {
+        self.players.iter()
+            .position(|p| p.chain_id == chain_id)
+            .ok_or_else(|| DoodleError::UnknownPlayer(chain_id.to_string()))
+    }
+
+    fn advance_to_next_round(&mut self) {
+        self.close_turn();
         if self.current_round < self.total_rounds {
             self.current_round += 1;
             self.game_state = GameState::ChoosingDrawer;
@@ -342,6 +1530,15 @@ impl GameRoom {
         }
     }
 
+    /// True once every active player except the drawer has guessed the word
+    pub fn all_guessers_done(&self) -> bool {
+        let mut guessers = self.players.iter()
+            .enumerate()
+            .filter(|(index, p)| Some(*index) != self.current_drawer_index && p.status == PlayerStatus::Active)
+            .peekable();
+        guessers.peek().is_some() && guessers.all(|(_, p)| p.has_guessed)
+    }
+
     pub fn has_all_players_drawn_in_round(&self) -> bool {
         if self.players.iter().all(|p| p.status != PlayerStatus::Active) {
             return false;
@@ -349,45 +1546,776 @@ impl GameRoom {
         
         match self.current_drawer_index {
             None => false,
-            Some(current_index) => self.next_active_index_after(current_index)
-                .zip(self.first_active_index())
-                .map(|(next, first)| next == first)
-                .unwrap_or(false),
+            // Drawers go in seat order, so the round is over once no active player sits after the current one
+            // (which may be a drawer who just left)
+            Some(current_index) => !self.players.iter()
+                .skip(current_index + 1)
+                .any(|p| p.status == PlayerStatus::Active),
+        }
+    }
+
+    pub fn ensure_host(&self, chain_id: ChainId) -> Result<(), DoodleError> {
+        if self.host_chain_id == chain_id {
+            Ok(())
+        } else {
+            Err(DoodleError::NotHost)
+        }
+    }
+
+    pub fn ensure_drawer(&self, chain_id: ChainId) -> Result<(), DoodleError> {
+        match self.get_current_drawer() {
+            Some(drawer) if drawer.chain_id == chain_id => Ok(()),
+            _ => Err(DoodleError::NotDrawer),
         }
     }
 
+    /// Active player who is not drawing
+    pub fn ensure_guesser(&self, chain_id: ChainId) -> Result<(), DoodleError> {
+        let index = self.players.iter()
+            .position(|p| p.chain_id == chain_id && p.status == PlayerStatus::Active)
+            .ok_or(DoodleError::NotActivePlayer)?;
+        if self.current_drawer_index == Some(index) {
+            return Err(DoodleError::DrawerCannotGuess);
+        }
+        Ok(())
+    }
+
+    /// Player in the room, other than the host, who has not left or been kicked
+    pub fn ensure_kickable(&self, chain_id: ChainId) -> Result<(), DoodleError> {
+        if chain_id == self.host_chain_id {
+            return Err(DoodleError::CannotKickHost);
+        }
+        match self.players.iter().find(|p| p.chain_id == chain_id) {
+            Some(player) if player.status == PlayerStatus::Active => Ok(()),
+            _ => Err(DoodleError::UnknownPlayer(chain_id.to_string())),
+        }
+    }
+
+    pub fn is_banned(&self, chain_id: ChainId) -> bool {
+        self.banned.contains(&chain_id)
+    }
+
+    pub fn active_player_count(&self) -> u32 {
+        self.players.iter().filter(|p| p.status == PlayerStatus::Active).count() as u32
+    }
+
+    /// Room capacity left for this chain (players already in the room keep their seat)
+    pub fn ensure_room_for(&self, chain_id: ChainId) -> Result<(), DoodleError> {
+        let seated = self.players.iter().any(|p| p.chain_id == chain_id && p.status == PlayerStatus::Active);
+        if !seated && self.active_player_count() >= self.settings.max_players() {
+            return Err(DoodleError::RoomFull);
+        }
+        Ok(())
+    }
+
+    /// A chain that is (or was, and was not kicked) a player in the room
+    pub fn is_returning(&self, chain_id: ChainId) -> bool {
+        self.players.iter().any(|p| p.chain_id == chain_id && p.status != PlayerStatus::Kicked)
+    }
+
+    /// Password hash after the host sets `password`: None keeps the current one, an empty one removes it
+    pub fn updated_password_hash(&self, password: Option<&str>) -> Option<String> {
+        match password {
+            None => self.settings.password_hash.clone(),
+            Some("") => None,
+            Some(password) => Some(password_hash(&self.room_id, password)),
+        }
+    }
+
+    /// Host side of a join request: ban list, capacity, privacy and password
+    /// Invited chains and returning players (not kicked) skip the privacy and password checks
+    pub fn check_join(&self, chain_id: ChainId, password: Option<&str>, invited: bool) -> Result<(), DoodleError> {
+        if self.is_banned(chain_id) {
+            return Err(DoodleError::Banned);
+        }
+        // Returning players get their seat back mid-game; invitations don't open a running game
+        if self.is_in_progress() && !self.is_returning(chain_id) {
+            return Err(DoodleError::GameAlreadyStarted);
+        }
+        self.ensure_room_for(chain_id)?;
+        if invited || self.is_returning(chain_id) {
+            return Ok(());
+        }
+        if self.settings.visibility == RoomVisibility::Private {
+            return Err(DoodleError::NotInvited);
+        }
+        if !self.settings.password_matches(&self.room_id, password) {
+            return Err(DoodleError::WrongPassword);
+        }
+        Ok(())
+    }
+
+    /// Settings can only change between games
+    pub fn ensure_lobby(&self) -> Result<(), DoodleError> {
+        if self.is_in_progress() {
+            return Err(DoodleError::WrongGameState { expected: GameState::WaitingForPlayers, actual: self.game_state });
+        }
+        Ok(())
+    }
+
+    pub fn ensure_state(&self, expected: GameState) -> Result<(), DoodleError> {
+        if self.game_state == expected {
+            Ok(())
+        } else {
+            Err(DoodleError::WrongGameState { expected, actual: self.game_state })
+        }
+    }
+
+    /// Deadline that has passed at `now` for the current turn, if any
+    pub fn expired_deadline(&self, now: Timestamp) -> Option<TurnEndReason> {
+        let passed = |since: Option<Timestamp>, seconds: u32| {
+            since.map_or(false, |start| now >= start.saturating_add(TimeDelta::from_secs(seconds as u64)))
+        };
+        match self.game_state {
+            GameState::Drawing if passed(self.word_chosen_at, self.seconds_per_round) => Some(TurnEndReason::DrawTimeout),
+            // An auto-pick request restarts the window so the drawer's chain has time to answer
+            GameState::WaitingForWord if passed(
+                self.auto_pick_requested_at.or(self.drawer_chosen_at),
+                self.settings.word_selection_seconds(),
+            ) => Some(TurnEndReason::WordSelectionTimeout),
+            _ => None,
+        }
+    }
+
+    /// Milliseconds since the word was chosen (0 if no word yet)
+    pub fn elapsed_millis(&self, now: Timestamp) -> u64 {
+        self.word_chosen_at.map_or(0, |chosen_at| now.delta_since(chosen_at).as_micros() / 1_000)
+    }
+
+    /// Points for a correct guess made now, using the room's scoring rules
+    pub fn guess_points(&self, elapsed_millis: u64) -> u32 {
+        let guessed_before = self.players.iter().filter(|p| p.has_guessed).count() as u32;
+        self.settings.scoring().guesser_points(guessed_before, elapsed_millis, self.seconds_per_round)
+    }
+
     pub fn get_current_drawer(&self) -> Option<&Player> {
         self.current_drawer_index.and_then(|index| self.players.get(index))
     }
 
-    pub fn award_points(&mut self, player_name: &str, points: u32) {
-        if let Some(player) = self.players.iter_mut().find(|p| p.name == player_name) {
-            player.score += points;
-            player.has_guessed = true;
-        }
+    /// The room's event stream on each member chain
+    /// Streams are already scoped to the emitting chain, so the host chain part of the room ID is left out
+    pub fn stream_name(&self) -> StreamName {
+        let local_id = self.room_id.split_once(':').map_or(self.room_id.as_str(), |(_, local_id)| local_id);
+        StreamName::from(format!("game_events_{}", local_id))
     }
 
-    pub fn end_match(&mut self, _timestamp: String) {
+    pub fn end_match(&mut self, _timestamp: Timestamp) {
         // Note: This method is now deprecated as endMatch completely deletes the room
         // instead of just resetting it. This is kept for backward compatibility.
         eprintln!("[DEPRECATED] end_match() called - room should be completely deleted instead");
     }
 
-    fn first_active_index(&self) -> Option<usize> {
-        self.players.iter().position(|p| p.status == PlayerStatus::Active)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Shared with the tests of the other modules
+    pub(crate) fn chain(index: u8) -> ChainId {
+        format!("{:064x}", index).parse().unwrap()
     }
 
-    fn next_active_index_after(&self, current_index: usize) -> Option<usize> {
-        if self.players.is_empty() {
-            return None;
+    fn host() -> ChainId {
+        chain(1)
+    }
+
+    fn member() -> ChainId {
+        chain(2)
+    }
+
+    fn outsider() -> ChainId {
+        chain(3)
+    }
+
+    fn player(chain_id: ChainId, name: &str) -> Player {
+        Player {
+            chain_id,
+            name: name.to_string(),
+            avatar_json: String::new(),
+            score: 0,
+            has_guessed: false,
+            status: PlayerStatus::Active,
         }
-        let players_len = self.players.len();
-        for offset in 1..=players_len {
-            let idx = (current_index + offset) % players_len;
-            if matches!(self.players.get(idx).map(|p| p.status), Some(PlayerStatus::Active)) {
-                return Some(idx);
-            }
+    }
+
+    // Room as replicated on every chain: host plus one joined player
+    fn lobby() -> GameRoom {
+        let mut room = GameRoom::new(host(), 1, "Host".to_string(), String::new(), RoomSettings::default(), Timestamp::from(0));
+        let room_id = room.room_id.clone();
+        room.apply(&DoodleEvent::PlayerJoined { room_id, player: player(member(), "Player"), timestamp: Timestamp::from(1) })
+            .unwrap();
+        room
+    }
+
+    // Game started with `drawer_index` drawing and the word already chosen
+    fn drawing(drawer_index: usize) -> GameRoom {
+        let mut room = lobby();
+        room.apply(&DoodleEvent::GameStarted {
+            room_id: room.room_id.clone(),
+            rounds: 1,
+            seconds_per_round: 60,
+            settings: RoomSettings::default(),
+            drawer_index,
+            drawer_name: room.players[drawer_index].name.clone(),
+            timestamp: Timestamp::from(2),
+        })
+        .unwrap();
+        room.apply(&DoodleEvent::WordChosen {
+            room_id: room.room_id.clone(),
+            commitment: "commitment".to_string(),
+            timestamp: Timestamp::from(3),
+            outcome: TurnOutcome::Played,
+        })
+        .unwrap();
+        room
+    }
+
+    fn start_game() -> Operation {
+        Operation::StartGame { rounds: 1, seconds_per_round: 60 }
+    }
+
+    fn guess_message(chain_id: ChainId, guess: &str, is_correct_guess: bool) -> ChatMessage {
+        ChatMessage {
+            player_chain_id: chain_id,
+            player_name: "Player".to_string(),
+            message: if is_correct_guess { "[Correct! +100 points]".to_string() } else { guess.to_string() },
+            is_correct_guess,
+            points_awarded: if is_correct_guess { 100 } else { 0 },
+            is_close: false,
+            elapsed_ms: 0,
+            sequence: 1,
+            // Committed with the salt the audit tests reveal
+            guess_commitment: is_correct_guess.then(|| word_commitment(guess, "salt")),
+        }
+    }
+
+    #[test]
+    fn guess_checks_use_a_salt_derived_from_the_secret() {
+        let check = GuessCheck::new("Ice cream", guess_check_salt("salt"), word_commitment("Ice cream", "salt"));
+        assert!(check.matches("ice-creams"));
+        assert!(!check.matches("ice"));
+        // Different from the commitment's salt, so the check hash doesn't match the public commitment
+        assert_ne!(check.salt, "salt");
+        assert_ne!(check.hash, check.commitment);
+        assert_ne!(guess_check_salt("salt"), guess_check_salt("other salt"));
+    }
+
+    #[test]
+    fn audit_checks_every_scored_guess() {
+        let commitment = word_commitment("cat", "salt");
+        let audit = |messages: &[ChatMessage], revealed: &[&str]| {
+            let mut turn = TurnAudit::new(1, host(), commitment.clone(), TurnOutcome::Played, GuessValidation::Drawer);
+            messages.iter().for_each(|message| turn.record(message));
+            turn.verify("cat", "salt", &revealed.iter().map(|guess| guess.to_string()).collect::<Vec<_>>())
+        };
+
+        assert_eq!(audit(&[guess_message(member(), "dog", false), guess_message(outsider(), "Cats", true)], &["Cats"]), AuditStatus::Verified);
+        // Right guess scored as wrong, wrong guess scored as correct, correct guess without a commitment
+        assert_eq!(audit(&[guess_message(member(), "cat", false)], &[]), AuditStatus::ScoringMismatch);
+        assert_eq!(audit(&[guess_message(member(), "dog", true)], &["dog"]), AuditStatus::ScoringMismatch);
+        let uncommitted = ChatMessage { guess_commitment: None, ..guess_message(member(), "cat", true) };
+        assert_eq!(audit(&[uncommitted], &["cat"]), AuditStatus::ScoringMismatch);
+        // Reveal that doesn't open the commitments made during the turn
+        assert_eq!(audit(&[guess_message(member(), "dog", true)], &["cat"]), AuditStatus::ScoringMismatch);
+        assert_eq!(audit(&[guess_message(member(), "cat", true)], &[]), AuditStatus::ScoringMismatch);
+
+        // The host scores host-validated rooms; the drawer's reveal has nothing to open
+        let mut turn = TurnAudit::new(1, host(), commitment.clone(), TurnOutcome::Played, GuessValidation::Host);
+        turn.record(&ChatMessage { guess_commitment: None, ..guess_message(member(), "cat", true) });
+        assert_eq!(turn.verify("cat", "salt", &[]), AuditStatus::Verified);
+
+        let mut turn = TurnAudit::new(1, host(), commitment.clone(), TurnOutcome::Played, GuessValidation::Drawer);
+        assert_eq!(turn.verify("dog", "salt", &[]), AuditStatus::CommitmentMismatch);
+    }
+
+    #[test]
+    fn non_host_replica_cannot_advance_game() {
+        let room = lobby();
+        assert_eq!(start_game().precheck(Some(&room), member(), &[]), Err(DoodleError::NotHost));
+        assert_eq!(start_game().precheck(Some(&room), host(), &[]), Ok(()));
+
+        let room = drawing(0);
+        for operation in [Operation::ChooseDrawer, Operation::EndMatch] {
+            assert_eq!(operation.precheck(Some(&room), member(), &[]), Err(DoodleError::NotHost));
+            assert_eq!(operation.precheck(Some(&room), host(), &[]), Ok(()));
+        }
+    }
+
+    #[test]
+    fn only_host_invites_to_room() {
+        let invite = Operation::InviteFriend { friend_chain_id: outsider().to_string() };
+        assert_eq!(invite.precheck(None, host(), &[]), Err(DoodleError::NoActiveRoom));
+        assert_eq!(invite.precheck(Some(&lobby()), member(), &[]), Err(DoodleError::NotHost));
+        assert_eq!(invite.precheck(Some(&lobby()), host(), &[]), Ok(()));
+    }
+
+    #[test]
+    fn only_current_drawer_chooses_word_and_publishes_hints() {
+        let mut room = drawing(1);
+        assert_eq!(Operation::PublishHint.precheck(Some(&room), host(), &[]), Err(DoodleError::NotDrawer));
+        assert_eq!(Operation::PublishHint.precheck(Some(&room), member(), &[]), Ok(()));
+
+        room.game_state = GameState::WaitingForWord;
+        let candidates = vec!["apple".to_string()];
+        let choose = Operation::ChooseWord { word: "apple".to_string(), salt: "salt".to_string() };
+        assert_eq!(choose.precheck(Some(&room), host(), &candidates), Err(DoodleError::NotDrawer));
+        assert_eq!(choose.precheck(Some(&room), member(), &candidates), Ok(()));
+        let unsalted = Operation::ChooseWord { word: "apple".to_string(), salt: String::new() };
+        assert_eq!(unsalted.precheck(Some(&room), member(), &candidates), Err(DoodleError::MissingSalt));
+        let reserve = Operation::ReserveWordSalt { salt: "salt".to_string() };
+        assert_eq!(reserve.precheck(Some(&room), host(), &[]), Err(DoodleError::NotDrawer));
+        assert_eq!(reserve.precheck(Some(&room), member(), &[]), Ok(()));
+        let unsalted = Operation::ReserveWordSalt { salt: String::new() };
+        assert_eq!(unsalted.precheck(Some(&room), member(), &[]), Err(DoodleError::MissingSalt));
+    }
+
+    #[test]
+    fn only_active_guessers_can_guess() {
+        let mut room = drawing(0);
+        let guess = Operation::GuessWord { guess: "apple".to_string() };
+        assert_eq!(guess.precheck(Some(&room), host(), &[]), Err(DoodleError::DrawerCannotGuess));
+        assert_eq!(guess.precheck(Some(&room), outsider(), &[]), Err(DoodleError::NotActivePlayer));
+        assert_eq!(guess.precheck(Some(&room), member(), &[]), Ok(()));
+
+        let left = DoodleEvent::PlayerLeft { room_id: room.room_id.clone(), player_chain_id: member(), timestamp: Timestamp::from(4) };
+        room.apply(&left).unwrap();
+        assert_eq!(guess.precheck(Some(&room), member(), &[]), Err(DoodleError::NotActivePlayer));
+    }
+
+    #[test]
+    fn display_names_are_unique_per_room() {
+        let mut room = lobby();
+        assert_eq!(room.unique_name(outsider(), "player"), "player (2)");
+        assert_eq!(room.unique_name(member(), "Player"), "Player");
+
+        let clash = DoodleEvent::PlayerJoined { room_id: room.room_id.clone(), player: player(outsider(), "Player"), timestamp: Timestamp::from(2) };
+        assert_eq!(room.apply(&clash), Err(DoodleError::NameTaken("Player".to_string())));
+
+        let renamed = DoodleEvent::PlayerJoined { room_id: room.room_id.clone(), player: player(outsider(), "Player (2)"), timestamp: Timestamp::from(2) };
+        room.apply(&renamed).unwrap();
+        assert_eq!(room.unique_name(chain(4), "Player"), "Player (3)");
+    }
+
+    #[test]
+    fn room_ids_are_unique_and_events_stay_in_their_room() {
+        let first = GameRoom::new(host(), 1, "Host".to_string(), String::new(), RoomSettings::default(), Timestamp::from(5));
+        let second = GameRoom::new(host(), 2, "Host".to_string(), String::new(), RoomSettings::default(), Timestamp::from(5));
+        let other_host = GameRoom::new(member(), 1, "Host".to_string(), String::new(), RoomSettings::default(), Timestamp::from(5));
+        assert_ne!(first.room_id, second.room_id);
+        assert_ne!(first.room_id, other_host.room_id);
+        assert_ne!(first.stream_name(), second.stream_name());
+
+        let mut room = lobby();
+        let stray = DoodleEvent::PlayerJoined { room_id: first.room_id.clone(), player: player(outsider(), "Stray"), timestamp: Timestamp::from(6) };
+        assert_eq!(room.apply(&stray), Err(DoodleError::WrongRoom(first.room_id.clone())));
+        assert!(room.players.iter().all(|p| p.chain_id != outsider()));
+    }
+
+    #[test]
+    fn host_kicks_and_bans_players() {
+        let kick = Operation::KickPlayer { player_chain_id: member().to_string() };
+        assert_eq!(kick.precheck(Some(&lobby()), member(), &[]), Err(DoodleError::NotHost));
+        assert_eq!(kick.precheck(Some(&lobby()), host(), &[]), Ok(()));
+        let kick_host = Operation::KickPlayer { player_chain_id: host().to_string() };
+        assert_eq!(kick_host.precheck(Some(&lobby()), host(), &[]), Err(DoodleError::CannotKickHost));
+        let kick_outsider = Operation::KickPlayer { player_chain_id: outsider().to_string() };
+        assert_eq!(kick_outsider.precheck(Some(&lobby()), host(), &[]), Err(DoodleError::UnknownPlayer(outsider().to_string())));
+        let ban_outsider = Operation::BanPlayer { player_chain_id: outsider().to_string() };
+        assert_eq!(ban_outsider.precheck(Some(&lobby()), host(), &[]), Ok(()));
+
+        // Kicking the drawer hands the turn back to the host, who moves on from the kicked drawer's seat
+        let mut room = drawing(1);
+        let room_id = room.room_id.clone();
+        room.apply(&DoodleEvent::PlayerKicked { room_id: room_id.clone(), player_chain_id: member(), banned: false, timestamp: Timestamp::from(4) })
+            .unwrap();
+        assert_eq!(room.players[1].status, PlayerStatus::Kicked);
+        assert_eq!(room.game_state, GameState::ChoosingDrawer);
+        assert_eq!(room.current_drawer_index, Some(1));
+        assert!(room.has_all_players_drawn_in_round());
+
+        // Kicked players may come back, banned ones may not
+        let rejoin = DoodleEvent::PlayerJoined { room_id: room_id.clone(), player: player(member(), "Player"), timestamp: Timestamp::from(5) };
+        room.apply(&rejoin).unwrap();
+        assert_eq!(room.players[1].status, PlayerStatus::Active);
+        room.apply(&DoodleEvent::PlayerKicked { room_id: room_id.clone(), player_chain_id: member(), banned: true, timestamp: Timestamp::from(6) })
+            .unwrap();
+        assert!(room.is_banned(member()));
+        assert_eq!(room.apply(&rejoin), Err(DoodleError::Banned));
+        assert_eq!(room.players[1].status, PlayerStatus::Kicked);
+    }
+
+    #[test]
+    fn rotation_continues_after_a_drawer_leaves() {
+        // Host, member and outsider seated in that order, `drawer_index` drawing when `leaver` leaves
+        let after_leaving = |drawer_index: usize, leaver: ChainId| {
+            let mut room = lobby();
+            let room_id = room.room_id.clone();
+            room.apply(&DoodleEvent::PlayerJoined { room_id: room_id.clone(), player: player(outsider(), "Third"), timestamp: Timestamp::from(1) })
+                .unwrap();
+            room.apply(&DoodleEvent::GameStarted {
+                room_id: room_id.clone(),
+                rounds: 2,
+                seconds_per_round: 60,
+                settings: RoomSettings::default(),
+                drawer_index,
+                drawer_name: room.players[drawer_index].name.clone(),
+                timestamp: Timestamp::from(2),
+            })
+            .unwrap();
+            room.apply(&DoodleEvent::PlayerLeft { room_id, player_chain_id: leaver, timestamp: Timestamp::from(3) })
+                .unwrap();
+            room
+        };
+
+        // Last seat leaves: the round is over, nobody draws twice
+        let room = after_leaving(2, outsider());
+        assert_eq!((room.game_state, room.current_drawer_index), (GameState::ChoosingDrawer, Some(2)));
+        assert!(room.has_all_players_drawn_in_round());
+
+        // Middle seat leaves: the next seat draws
+        let room = after_leaving(1, member());
+        assert!(!room.has_all_players_drawn_in_round());
+        assert_eq!(room.next_drawer_index(), Some(2));
+
+        // First seat leaves: the others still get their turn
+        let room = after_leaving(0, host());
+        assert!(!room.has_all_players_drawn_in_round());
+        assert_eq!(room.next_drawer_index(), Some(1));
+
+        // A guesser leaving doesn't touch the turn
+        let room = after_leaving(0, member());
+        assert_eq!((room.game_state, room.current_drawer_index), (GameState::WaitingForWord, Some(0)));
+    }
+
+    #[test]
+    fn join_requests_follow_room_settings() {
+        let mut room = lobby();
+        room.settings.max_players = Some(3);
+        room.settings.password_hash = room.updated_password_hash(Some("secret"));
+        assert_eq!(room.check_join(outsider(), None, false), Err(DoodleError::WrongPassword));
+        assert_eq!(room.check_join(outsider(), Some("guess"), false), Err(DoodleError::WrongPassword));
+        assert_eq!(room.check_join(outsider(), Some("secret"), false), Ok(()));
+        // Salted with the room ID: the same password hashes differently in another room
+        assert_ne!(room.settings.password_hash, Some(password_hash("other room", "secret")));
+        assert_eq!(room.updated_password_hash(None), room.settings.password_hash);
+        assert_eq!(room.updated_password_hash(Some("")), None);
+        // Invited chains and players already in the room don't need the password
+        assert_eq!(room.check_join(outsider(), None, true), Ok(()));
+        assert_eq!(room.check_join(member(), None, false), Ok(()));
+
+        room.settings.visibility = RoomVisibility::Private;
+        assert_eq!(room.check_join(outsider(), Some("secret"), false), Err(DoodleError::NotInvited));
+
+        let room_id = room.room_id.clone();
+        room.apply(&DoodleEvent::PlayerJoined { room_id: room_id.clone(), player: player(outsider(), "Third"), timestamp: Timestamp::from(2) })
+            .unwrap();
+        assert_eq!(room.check_join(chain(4), None, true), Err(DoodleError::RoomFull));
+        let fourth = DoodleEvent::PlayerJoined { room_id, player: player(chain(4), "Fourth"), timestamp: Timestamp::from(3) };
+        assert_eq!(room.apply(&fourth), Err(DoodleError::RoomFull));
+    }
+
+    #[test]
+    fn settings_change_only_in_the_lobby() {
+        let settings = RoomSettings { max_players: Some(4), ..RoomSettings::default() };
+        let update = Operation::UpdateSettings { settings: settings.clone(), password: None };
+        assert_eq!(update.precheck(Some(&lobby()), member(), &[]), Err(DoodleError::NotHost));
+        assert_eq!(update.precheck(Some(&lobby()), host(), &[]), Ok(()));
+        assert!(matches!(update.precheck(Some(&drawing(0)), host(), &[]), Err(DoodleError::WrongGameState { .. })));
+
+        // Every test room has the same ID, so one event fits both
+        let changed = DoodleEvent::SettingsChanged { room_id: lobby().room_id, settings, timestamp: Timestamp::from(4) };
+        let mut room = drawing(0);
+        assert!(matches!(room.apply(&changed), Err(DoodleError::IllegalTransition { .. })));
+        let mut room = lobby();
+        room.apply(&changed).unwrap();
+        assert_eq!(room.settings.max_players(), 4);
+    }
+
+    #[test]
+    fn word_filters_must_leave_some_words() {
+        let bank = word_bank::builtin_bank(word_bank::DEFAULT_WORD_BANK).unwrap();
+        let settings = |category: &str, difficulty| RoomSettings {
+            word_category: Some(category.to_string()),
+            word_difficulty: Some(difficulty),
+            ..RoomSettings::default()
+        };
+        assert_eq!(settings("food", Difficulty::Hard).validate(&bank), Ok(()));
+        assert!(settings("actions", Difficulty::Easy).validate(&bank).unwrap_err().contains("filters"));
+        assert!(settings("space", Difficulty::Easy).validate(&bank).unwrap_err().contains("Unknown category"));
+    }
+
+    #[test]
+    fn refused_joins_have_a_reason() {
+        let mut room = lobby();
+        room.settings.visibility = RoomVisibility::Private;
+        let reason = room.check_join(outsider(), None, false).map_err(|error| JoinRejectReason::from_error(&error));
+        assert_eq!(reason, Err(JoinRejectReason::Private));
+        assert_eq!(JoinRejectReason::from_error(&DoodleError::NoActiveRoom), JoinRejectReason::NoRoom);
+
+        // Once the game runs only players who were already in the room get back in, invited or not
+        let room = drawing(0);
+        let reason = room.check_join(outsider(), None, true).map_err(|error| JoinRejectReason::from_error(&error));
+        assert_eq!(reason, Err(JoinRejectReason::GameStarted));
+        assert_eq!(room.check_join(member(), None, false), Ok(()));
+
+        let mut pending = PendingJoin { host_chain_id: host(), room_id: None, requested_at: Timestamp::from(1), rejection: None };
+        assert_eq!(pending.status(), "joining…");
+        pending.rejection = Some(JoinRejectReason::RoomFull);
+        assert_eq!(pending.status(), "rejected: room full");
+    }
+
+    #[test]
+    fn join_applications_wait_for_the_host() {
+        let mut room = lobby();
+        room.settings.approval_required = true;
+        assert!(!room.is_returning(outsider()));
+        assert!(room.is_returning(member()));
+
+        let approve = Operation::ApproveJoin { player_chain_id: outsider().to_string() };
+        assert_eq!(approve.precheck(Some(&room), member(), &[]), Err(DoodleError::NotHost));
+        assert_eq!(approve.precheck(Some(&room), host(), &[]), Ok(()));
+        let reject = Operation::RejectJoin { player_chain_id: "not a chain".to_string() };
+        assert!(matches!(reject.precheck(Some(&room), host(), &[]), Err(DoodleError::InvalidChainId(_))));
+
+        let application = JoinApplication {
+            chain_id: outsider(),
+            player_name: "Eve".to_string(),
+            avatar_json: String::new(),
+            requested_at: Timestamp::from(1_000_000),
+        };
+        let timeout = JOIN_APPROVAL_TIMEOUT_SECONDS * 1_000_000;
+        assert!(!application.is_expired(Timestamp::from(timeout)));
+        assert!(application.is_expired(Timestamp::from(1_000_000 + timeout)));
+    }
+
+    #[test]
+    fn events_out_of_order_are_rejected() {
+        let mut room = lobby();
+        let room_id = room.room_id.clone();
+
+        // Chat and words only while a turn is running, drawers only once the game started
+        let chat = DoodleEvent::ChatMessage { room_id: room_id.clone(), message: guess_message(member(), "dog", false) };
+        assert!(matches!(room.apply(&chat), Err(DoodleError::IllegalTransition { .. })));
+        let drawer = DoodleEvent::DrawerChosen {
+            room_id: room_id.clone(),
+            drawer_index: 1,
+            drawer_name: "Player".to_string(),
+            timestamp: Timestamp::from(2),
+            previous_blob_hash: None,
+            reason: TurnEndReason::HostAdvanced,
+        };
+        assert!(matches!(room.apply(&drawer), Err(DoodleError::IllegalTransition { .. })));
+        let word = DoodleEvent::WordChosen {
+            room_id: room_id.clone(),
+            commitment: "commitment".to_string(),
+            timestamp: Timestamp::from(3),
+            outcome: TurnOutcome::Played,
+        };
+        assert!(matches!(room.apply(&word), Err(DoodleError::IllegalTransition { .. })));
+        assert_eq!(room.game_state, GameState::WaitingForPlayers);
+        assert!(room.chat_messages.is_empty() && room.current_drawer_index.is_none());
+
+        // Once a word is chosen, another one can't be until the next drawer
+        let mut room = drawing(0);
+        let word = DoodleEvent::WordChosen {
+            room_id: room.room_id.clone(),
+            commitment: "another".to_string(),
+            timestamp: Timestamp::from(4),
+            outcome: TurnOutcome::Played,
+        };
+        assert!(matches!(room.apply(&word), Err(DoodleError::IllegalTransition { .. })));
+    }
+
+    #[test]
+    fn replicas_converge_on_the_host_scores() {
+        // A replica that drifted (missed a guess, or counted one twice) takes the host's totals
+        let mut room = drawing(0);
+        room.total_rounds = 2;
+        room.players[1].score = 999;
+        let scores = vec![(host(), 50), (member(), 120)];
+        room.apply(&DoodleEvent::RoundEnded {
+            room_id: room.room_id.clone(),
+            scores: scores.clone(),
+            reason: TurnEndReason::DrawTimeout,
+            timestamp: Timestamp::from(5),
+        })
+        .unwrap();
+        assert_eq!(room.players.iter().map(|p| (p.chain_id, p.score)).collect::<Vec<_>>(), scores);
+
+        let mut room = drawing(0);
+        room.players[0].score = 7;
+        let final_scores = vec![(host(), 80), (member(), 200)];
+        room.apply(&DoodleEvent::GameEnded {
+            room_id: room.room_id.clone(),
+            final_scores: final_scores.clone(),
+            reason: TurnEndReason::DrawTimeout,
+            timestamp: Timestamp::from(5),
+        })
+        .unwrap();
+        assert_eq!(room.players.iter().map(|p| (p.chain_id, p.score)).collect::<Vec<_>>(), final_scores);
+    }
+
+    #[test]
+    fn auto_pick_is_requested_once_per_turn() {
+        let mut room = lobby();
+        room.apply(&DoodleEvent::GameStarted {
+            room_id: room.room_id.clone(),
+            rounds: 1,
+            seconds_per_round: 60,
+            settings: RoomSettings::default(),
+            drawer_index: 0,
+            drawer_name: "Host".to_string(),
+            timestamp: Timestamp::from(2),
+        })
+        .unwrap();
+        let request = |drawer_index| DoodleEvent::AutoPickRequested { room_id: lobby().room_id, drawer_index, timestamp: Timestamp::from(3) };
+        assert!(matches!(room.apply(&request(1)), Err(DoodleError::IllegalTransition { .. })));
+        room.apply(&request(0)).unwrap();
+        assert_eq!(room.auto_pick_requested_at, Some(Timestamp::from(3)));
+        assert!(matches!(room.apply(&request(0)), Err(DoodleError::IllegalTransition { .. })));
+        // Not once the word is chosen
+        assert!(matches!(drawing(0).apply(&request(0)), Err(DoodleError::IllegalTransition { .. })));
+    }
+
+    #[test]
+    fn chat_messages_apply_once_in_sequence() {
+        let mut room = drawing(0);
+        let room_id = room.room_id.clone();
+        let chat = |sequence: u64, message: ChatMessage| DoodleEvent::ChatMessage {
+            room_id: room_id.clone(),
+            message: ChatMessage { sequence, ..message },
+        };
+
+        // The same wrong guess twice is two messages; a relayed copy of either is not
+        room.apply(&chat(1, guess_message(member(), "dog", false))).unwrap();
+        room.apply(&chat(2, guess_message(member(), "dog", false))).unwrap();
+        room.apply(&chat(2, guess_message(member(), "dog", false))).unwrap();
+        assert_eq!(room.chat_messages.len(), 2);
+
+        // Still recognized once the original was trimmed from the chat
+        for sequence in 3..=12 {
+            room.apply(&chat(sequence, guess_message(member(), "dog", false))).unwrap();
         }
-        None
+        room.apply(&chat(1, guess_message(member(), "dog", false))).unwrap();
+        assert_eq!(room.chat_messages.len(), 10);
+        assert_eq!(room.chat_messages.last().map(|m| m.sequence), Some(12));
+
+        // Awards are capped at the room's maximum points and the score saturates
+        let greedy = ChatMessage { points_awarded: u32::MAX, ..guess_message(member(), "cat", true) };
+        room.apply(&chat(13, greedy.clone())).unwrap();
+        assert_eq!(room.players[1].score, ScoringRules::default().max_points);
+
+        let mut room = drawing(0);
+        room.players[1].score = u32::MAX - 5;
+        room.apply(&DoodleEvent::ChatMessage { room_id: room.room_id.clone(), message: greedy }).unwrap();
+        assert_eq!(room.players[1].score, u32::MAX);
+    }
+
+    #[test]
+    fn guessers_score_less_later_and_after_others() {
+        let rules = ScoringRules::default();
+        // Half of max_points decays over the turn, the other half is kept
+        assert_eq!(rules.guesser_points(0, 0, 60), 100);
+        assert_eq!(rules.guesser_points(0, 30_000, 60), 75);
+        assert_eq!(rules.guesser_points(0, 60_000, 60), 50);
+        // Late guesses (past the drawing time) keep the undecayed half
+        assert_eq!(rules.guesser_points(0, 120_000, 60), 50);
+        // Each earlier guesser costs order_penalty, down to min_points
+        assert_eq!(rules.guesser_points(2, 30_000, 60), 55);
+        assert_eq!(rules.guesser_points(10, 120_000, 60), 10);
+        // Without a drawing time there is nothing to decay
+        assert_eq!(rules.guesser_points(0, 30_000, 0), 100);
+
+        let flat = ScoringRules { time_weight_percent: 0, ..ScoringRules::default() };
+        assert_eq!(flat.guesser_points(0, 59_000, 60), 100);
+        let timed = ScoringRules { time_weight_percent: 250, ..ScoringRules::default() };
+        assert_eq!(timed.guesser_points(0, 60_000, 60), 10);
+    }
+
+    #[test]
+    fn drawers_score_by_share_and_speed_of_guesses() {
+        let rules = ScoringRules::default();
+        // Nobody guessed, with or without anyone left to guess
+        assert_eq!(rules.drawer_points(&[], 3, 60), -20);
+        assert_eq!(rules.drawer_points(&[], 0, 60), -20);
+        // Everyone at once earns the full award, everyone slowly half of it
+        assert_eq!(rules.drawer_points(&[0, 0, 0], 3, 60), 100);
+        assert_eq!(rules.drawer_points(&[30_000, 30_000], 2, 60), 75);
+        assert_eq!(rules.drawer_points(&[60_000], 2, 60), 25);
+        // Guesses past the drawing time count as slowest, never negative
+        assert_eq!(rules.drawer_points(&[120_000], 1, 60), 50);
+        // Guessers who left since still count towards the share
+        assert_eq!(rules.drawer_points(&[0], 0, 60), 100);
+        assert_eq!(rules.drawer_points(&[45_000], 1, 0), 100);
+    }
+
+    #[test]
+    fn deadlines_expire_after_their_window() {
+        let after = |start: u64, seconds: u64| Timestamp::from(start).saturating_add(TimeDelta::from_secs(seconds));
+
+        // Nothing to time out in the lobby
+        assert_eq!(lobby().expired_deadline(after(0, 3_600)), None);
+
+        // Drawing time runs from WordChosen
+        let room = drawing(0);
+        assert_eq!(room.expired_deadline(after(3, 59)), None);
+        assert_eq!(room.expired_deadline(after(3, 60)), Some(TurnEndReason::DrawTimeout));
+
+        // Word selection runs from DrawerChosen, and restarts when the host asks for an auto-pick
+        let mut room = lobby();
+        room.apply(&DoodleEvent::GameStarted {
+            room_id: room.room_id.clone(),
+            rounds: 1,
+            seconds_per_round: 60,
+            settings: RoomSettings::default(),
+            drawer_index: 0,
+            drawer_name: "Host".to_string(),
+            timestamp: Timestamp::from(2),
+        })
+        .unwrap();
+        let window = room.settings.word_selection_seconds() as u64;
+        assert_eq!(room.expired_deadline(after(2, window - 1)), None);
+        assert_eq!(room.expired_deadline(after(2, window)), Some(TurnEndReason::WordSelectionTimeout));
+        room.apply(&DoodleEvent::AutoPickRequested {
+            room_id: room.room_id.clone(),
+            drawer_index: 0,
+            timestamp: after(2, window),
+        })
+        .unwrap();
+        assert_eq!(room.expired_deadline(after(2, window)), None);
+        assert_eq!(room.expired_deadline(after(2, 2 * window)), Some(TurnEndReason::WordSelectionTimeout));
+    }
+
+    #[test]
+    fn turn_ends_when_every_active_guesser_guessed() {
+        let mut room = lobby();
+        for index in [3, 4] {
+            let player = player(chain(index), &format!("Player {}", index));
+            room.apply(&DoodleEvent::PlayerJoined { room_id: room.room_id.clone(), player, timestamp: Timestamp::from(1) })
+                .unwrap();
+        }
+        room.apply(&DoodleEvent::GameStarted {
+            room_id: room.room_id.clone(),
+            rounds: 1,
+            seconds_per_round: 60,
+            settings: RoomSettings::default(),
+            drawer_index: 0,
+            drawer_name: "Host".to_string(),
+            timestamp: Timestamp::from(2),
+        })
+        .unwrap();
+        assert!(!room.all_guessers_done());
+
+        // The drawer never guesses; players who left or were kicked are not waited for
+        room.players[1].has_guessed = true;
+        assert!(!room.all_guessers_done());
+        room.players[2].status = PlayerStatus::Left;
+        room.players[3].status = PlayerStatus::Kicked;
+        assert!(room.all_guessers_done());
+
+        // A drawer alone has nobody to wait for, which is not "everyone guessed"
+        room.players[1].status = PlayerStatus::Left;
+        assert!(!room.all_guessers_done());
     }
 }
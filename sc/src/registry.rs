@@ -0,0 +1,336 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+/*! Directory of public rooms and quick-play matchmaking, kept by the registry chain
+
+Hosts of public rooms send their listing to the registry chain named in the application
+parameters (register, then heartbeats on change or every few minutes, then unregister).
+The registry drops listings it has not heard about for `LISTING_STALE_SECONDS`.
+Heartbeats only go out when the host chain executes a block, so an idle lobby relies on the
+host's client calling `tick`; a lobby whose host went away drops off the directory.
+
+The registry is also the matchmaker: players queue with their preferences and are sent to an
+open listed room, or grouped with other queued players once `MATCH_PLAYERS` are waiting. */
+
+use linera_sdk::linera_base_types::{ChainId, TimeDelta, Timestamp};
+use serde::{Deserialize, Serialize};
+
+use crate::word_bank;
+use crate::{CrossChainMessage, GameRoom, GameState, RoomSettings, RoomVisibility};
+
+// Hosts refresh an unchanged listing this often (whenever their chain runs a block)
+pub const HEARTBEAT_SECONDS: u64 = 120;
+// Listings without a heartbeat for this long are removed
+pub const LISTING_STALE_SECONDS: u64 = 600;
+
+// publicRooms page size
+pub const DEFAULT_PAGE_SIZE: u32 = 20;
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+// Queued players grouped into a new room (the oldest one hosts)
+pub const MATCH_PLAYERS: usize = 3;
+// Rounds of a matched game when nobody in the group asked for a number
+pub const DEFAULT_MATCH_ROUNDS: u32 = 3;
+// Queued players nobody was matched with for this long are dropped
+pub const MATCH_QUEUE_TIMEOUT_SECONDS: u64 = 600;
+
+// Public room as shown in the lobby browser
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, async_graphql::SimpleObject)]
+#[graphql(rename_fields = "camelCase")]
+pub struct RoomListing {
+    pub room_id: String,
+    pub host_chain_id: ChainId,
+    pub host_name: String,
+    pub player_count: u32,
+    pub max_players: u32,
+    pub game_state: GameState,
+    pub has_password: bool,
+    pub settings: RoomSettings, // Without the password hash
+    pub updated_at: Timestamp, // Host's clock when sent, registry's clock once stored
+}
+
+impl RoomListing {
+    /// Listing of a room, or None if the room should not be listed (private rooms)
+    pub fn of(room: &GameRoom, now: Timestamp) -> Option<RoomListing> {
+        if room.settings.visibility != RoomVisibility::Public {
+            return None;
+        }
+        let host_name = room.players.iter()
+            .find(|p| p.chain_id == room.host_chain_id)
+            .map(|p| p.name.clone())
+            .unwrap_or_default();
+        Some(RoomListing {
+            room_id: room.room_id.clone(),
+            host_chain_id: room.host_chain_id,
+            host_name,
+            player_count: room.active_player_count(),
+            max_players: room.settings.max_players(),
+            game_state: room.game_state,
+            has_password: room.settings.password_hash.is_some(),
+            settings: RoomSettings { password_hash: None, ..room.settings.clone() },
+            updated_at: now,
+        })
+    }
+
+    pub fn is_stale(&self, now: Timestamp) -> bool {
+        now >= self.updated_at.saturating_add(TimeDelta::from_secs(LISTING_STALE_SECONDS))
+    }
+
+    /// Case-insensitive match on host name, language, word bank and category
+    pub fn matches(&self, search: &str) -> bool {
+        let search = search.trim().to_lowercase();
+        if search.is_empty() {
+            return true;
+        }
+        [
+            Some(self.host_name.as_str()),
+            self.settings.language.as_deref(),
+            Some(self.settings.word_bank_id()),
+            self.settings.word_category.as_deref(),
+        ]
+        .into_iter()
+        .flatten()
+        .any(|field| field.to_lowercase().contains(&search))
+    }
+
+    /// Same room, same content (only the timestamp may differ)
+    fn same_as(&self, other: &RoomListing) -> bool {
+        RoomListing { updated_at: other.updated_at, ..self.clone() } == *other
+    }
+}
+
+/// Messages a host sends the registry when its listing goes from `listed` (last sent) to `next`
+/// Empty when the registry is up to date and no heartbeat is due
+pub fn updates(listed: Option<&RoomListing>, next: Option<&RoomListing>) -> Vec<CrossChainMessage> {
+    let mut messages = Vec::new();
+    let same_room = |old: &RoomListing| next.map_or(false, |next| next.room_id == old.room_id);
+    match (listed, next) {
+        (Some(old), Some(next)) if same_room(old) => {
+            let heartbeat_due = next.updated_at >= old.updated_at.saturating_add(TimeDelta::from_secs(HEARTBEAT_SECONDS));
+            if !old.same_as(next) || heartbeat_due {
+                messages.push(CrossChainMessage::RoomHeartbeat { listing: next.clone() });
+            }
+        }
+        (listed, next) => {
+            if let Some(old) = listed {
+                messages.push(CrossChainMessage::UnregisterRoom { room_id: old.room_id.clone() });
+            }
+            if let Some(next) = next {
+                messages.push(CrossChainMessage::RegisterRoom { listing: next.clone() });
+            }
+        }
+    }
+    messages
+}
+
+// One page of publicRooms results
+#[derive(Debug, Clone, Serialize, Deserialize, async_graphql::SimpleObject)]
+#[graphql(rename_fields = "camelCase")]
+pub struct PublicRoomsPage {
+    pub rooms: Vec<RoomListing>,
+    pub total: u32, // Matching rooms across all pages
+    pub offset: u32,
+    pub has_more: bool,
+}
+
+/// Fresh listings matching `search`, most recently updated first, `limit` from `offset`
+pub fn page(listings: &[RoomListing], search: Option<&str>, now: Timestamp, offset: u32, limit: Option<u32>) -> PublicRoomsPage {
+    let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE) as usize;
+    let mut matching = listings.iter()
+        .filter(|listing| !listing.is_stale(now) && search.map_or(true, |search| listing.matches(search)))
+        .collect::<Vec<_>>();
+    matching.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+    let rooms = matching.iter().skip(offset as usize).take(limit).map(|listing| (*listing).clone()).collect::<Vec<_>>();
+    PublicRoomsPage {
+        has_more: (offset as usize).saturating_add(rooms.len()) < matching.len(),
+        total: matching.len() as u32,
+        offset,
+        rooms,
+    }
+}
+
+// What a quick-play player is looking for
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, async_graphql::SimpleObject, async_graphql::InputObject)]
+#[graphql(rename_fields = "camelCase", input_name = "MatchPreferencesInput")]
+pub struct MatchPreferences {
+    pub language: Option<String>, // Any language when None
+    pub rounds: Option<u32>, // Only used when a new room is created for the match
+}
+
+impl MatchPreferences {
+    fn accepts_language(&self, language: Option<&str>) -> bool {
+        match (self.language.as_deref(), language) {
+            (None, _) | (_, None) => true,
+            (Some(wanted), Some(language)) => wanted.eq_ignore_ascii_case(language),
+        }
+    }
+}
+
+// Player waiting for a match (kept by the matchmaker, and by the player until it is matched)
+#[derive(Debug, Clone, Serialize, Deserialize, async_graphql::SimpleObject)]
+#[graphql(rename_fields = "camelCase")]
+pub struct MatchTicket {
+    pub chain_id: ChainId,
+    pub player_name: String,
+    pub avatar_json: String,
+    pub preferences: MatchPreferences,
+    pub queued_at: Timestamp,
+}
+
+impl MatchTicket {
+    pub fn is_expired(&self, now: Timestamp) -> bool {
+        now >= self.queued_at.saturating_add(TimeDelta::from_secs(MATCH_QUEUE_TIMEOUT_SECONDS))
+    }
+}
+
+/// Listed room a quick-play player can be sent to: in the lobby, with a free seat, no password,
+/// no approval step and a language the player accepts. Fullest room first, to get games going
+pub fn open_room<'a>(listings: &'a [RoomListing], preferences: &MatchPreferences, now: Timestamp) -> Option<&'a RoomListing> {
+    listings.iter()
+        .filter(|listing| {
+            !listing.is_stale(now)
+                && listing.game_state == GameState::WaitingForPlayers
+                && listing.player_count < listing.max_players
+                && !listing.has_password
+                && !listing.settings.approval_required
+                && preferences.accepts_language(listing.settings.language.as_deref())
+        })
+        .max_by_key(|listing| listing.player_count)
+}
+
+/// Oldest queued players that can play with `ticket` (which must be in `queue`), once there are enough
+/// The first one hosts; they all share a language if any of them asked for one
+pub fn take_match(queue: &mut Vec<MatchTicket>, ticket: &MatchTicket) -> Option<Vec<MatchTicket>> {
+    let mut language = ticket.preferences.language.clone();
+    let mut group = Vec::new();
+    for queued in queue.iter() {
+        if group.len() == MATCH_PLAYERS {
+            break;
+        }
+        if queued.preferences.accepts_language(language.as_deref()) {
+            language = language.or_else(|| queued.preferences.language.clone());
+            group.push(queued.chain_id);
+        }
+    }
+    if group.len() < MATCH_PLAYERS || !group.contains(&ticket.chain_id) {
+        return None;
+    }
+    let (matched, waiting) = std::mem::take(queue).into_iter().partition(|queued| group.contains(&queued.chain_id));
+    *queue = waiting;
+    Some(matched)
+}
+
+/// Settings and rounds for a room created for `group`: the group's language (with its built-in
+/// word bank, if there is one) and the rounds asked for by the oldest player who chose
+pub fn match_room(group: &[MatchTicket]) -> (RoomSettings, u32) {
+    let language = group.iter().find_map(|ticket| ticket.preferences.language.clone());
+    let word_bank = language.as_deref().and_then(|language| {
+        word_bank::builtin_banks().into_iter().find(|bank| bank.language.eq_ignore_ascii_case(language)).map(|bank| bank.id)
+    });
+    let rounds = group.iter().find_map(|ticket| ticket.preferences.rounds).unwrap_or(DEFAULT_MATCH_ROUNDS);
+    let settings = RoomSettings {
+        language,
+        word_bank,
+        ..RoomSettings::default()
+    };
+    (settings, rounds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::chain;
+    use crate::{password_hash, DoodleError, Operation};
+
+    // Lobby of a public room hosted by `chain(index)`
+    fn room(index: u8) -> GameRoom {
+        GameRoom::new(chain(index), 1, format!("Host {}", index), String::new(), RoomSettings::default(), Timestamp::from(0))
+    }
+
+    #[test]
+    fn hosts_keep_the_registry_listing_current() {
+        let mut room = room(1);
+        room.settings.password_hash = Some(password_hash(&room.room_id, "secret"));
+        let listing = RoomListing::of(&room, Timestamp::from(0)).unwrap();
+        assert_eq!((listing.host_name.as_str(), listing.player_count), ("Host 1", 1));
+        assert!(listing.has_password && listing.settings.password_hash.is_none());
+
+        let register = updates(None, Some(&listing));
+        assert!(matches!(register.as_slice(), [CrossChainMessage::RegisterRoom { .. }]));
+        // Nothing changed and no heartbeat due yet
+        let later = RoomListing { updated_at: Timestamp::from(1_000_000), ..listing.clone() };
+        assert!(updates(Some(&listing), Some(&later)).is_empty());
+        let joined = RoomListing { player_count: 2, ..later.clone() };
+        assert!(matches!(updates(Some(&listing), Some(&joined)).as_slice(), [CrossChainMessage::RoomHeartbeat { .. }]));
+        let due = RoomListing { updated_at: Timestamp::from(HEARTBEAT_SECONDS * 1_000_000), ..listing.clone() };
+        assert!(matches!(updates(Some(&listing), Some(&due)).as_slice(), [CrossChainMessage::RoomHeartbeat { .. }]));
+
+        // Going private (or closing the room) takes it off the directory
+        room.settings.visibility = RoomVisibility::Private;
+        assert!(RoomListing::of(&room, Timestamp::from(0)).is_none());
+        assert!(matches!(updates(Some(&listing), None).as_slice(), [CrossChainMessage::UnregisterRoom { .. }]));
+        assert!(updates(None, None).is_empty());
+    }
+
+    #[test]
+    fn public_rooms_are_searchable_and_paginated() {
+        let listing = |index: u8, language: &str, updated_secs: u64| {
+            let mut room = room(index);
+            room.settings.language = Some(language.to_string());
+            room.settings.word_bank = Some(format!("classic_{}", language));
+            RoomListing::of(&room, Timestamp::from(updated_secs * 1_000_000)).unwrap()
+        };
+        let listings = vec![listing(1, "en", 700), listing(2, "uk", 650), listing(3, "en", 690), listing(4, "en", 10)];
+        let now = Timestamp::from(700 * 1_000_000);
+
+        // Room 4 is stale; newest first
+        let first = page(&listings, Some(" EN "), now, 0, Some(1));
+        assert_eq!((first.total, first.has_more), (2, true));
+        assert_eq!(first.rooms[0].host_chain_id, chain(1));
+        let second = page(&listings, Some("en"), now, 1, Some(1));
+        assert_eq!((second.rooms[0].host_chain_id, second.has_more), (chain(3), false));
+
+        assert_eq!(page(&listings, Some("host 2"), now, 0, None).total, 1);
+        assert_eq!(page(&listings, None, now, 5, None).rooms.len(), 0);
+    }
+
+    #[test]
+    fn matchmaker_fills_rooms_then_groups_queued_players() {
+        let preferences = |language: Option<&str>| MatchPreferences { language: language.map(str::to_string), rounds: None };
+        let ticket = |index: u8, language: Option<&str>| MatchTicket {
+            chain_id: chain(index),
+            player_name: format!("Player {}", index),
+            avatar_json: String::new(),
+            preferences: preferences(language),
+            queued_at: Timestamp::from(0),
+        };
+        let queue_for_match = Operation::QueueForMatch { player_name: "A".to_string(), avatar_json: String::new(), preferences: preferences(None) };
+        assert_eq!(queue_for_match.precheck(Some(&room(1)), chain(2), &[]), Err(DoodleError::AlreadyInRoom));
+        assert_eq!(queue_for_match.precheck(None, chain(2), &[]), Ok(()));
+
+        // Open public room with a free seat in the player's language
+        let mut open = RoomListing::of(&room(1), Timestamp::from(0)).unwrap();
+        open.settings.language = Some("en".to_string());
+        let mut locked = RoomListing { room_id: "locked".to_string(), has_password: true, player_count: 3, ..open.clone() };
+        let listings = vec![open.clone(), locked.clone()];
+        assert_eq!(open_room(&listings, &preferences(Some("EN")), Timestamp::from(0)), Some(&open));
+        assert_eq!(open_room(&listings, &preferences(Some("uk")), Timestamp::from(0)), None);
+        locked.has_password = false;
+        assert_eq!(open_room(&[open.clone(), locked.clone()], &preferences(None), Timestamp::from(0)), Some(&locked));
+
+        // No room: wait until MATCH_PLAYERS compatible players are queued
+        let mut queue = vec![ticket(10, Some("uk")), ticket(11, None)];
+        let en = ticket(12, Some("en"));
+        queue.push(en.clone());
+        assert!(take_match(&mut queue, &en).is_none());
+        let last = ticket(13, Some("uk"));
+        queue.push(last.clone());
+        let group = take_match(&mut queue, &last).unwrap();
+        assert_eq!(group.iter().map(|t| t.chain_id).collect::<Vec<_>>(), vec![chain(10), chain(11), chain(13)]);
+        assert_eq!(queue.iter().map(|t| t.chain_id).collect::<Vec<_>>(), vec![chain(12)]);
+
+        let (settings, rounds) = match_room(&group);
+        assert_eq!((settings.language.as_deref(), settings.word_bank.as_deref()), (Some("uk"), Some("classic_uk")));
+        assert_eq!(rounds, DEFAULT_MATCH_ROUNDS);
+    }
+}
@@ -8,7 +8,7 @@ mod state;
 use std::sync::Arc;
 
 use async_graphql::{ComplexObject, EmptySubscription, Object, Request, Response, Schema};
-use linera_sdk::{linera_base_types::WithServiceAbi, views::View, Service, ServiceRuntime};
+use linera_sdk::{linera_base_types::{ChainId, Timestamp, WithServiceAbi}, views::View, Service, ServiceRuntime};
 use doodle_game::{DoodleGameAbi};
 
 use self::state::DoodleGameState;
@@ -25,7 +25,7 @@ impl WithServiceAbi for DoodleGameService {
 }
 
 impl Service for DoodleGameService {
-    type Parameters = ();
+    type Parameters = doodle_game::DoodleParameters;
 
     async fn new(runtime: ServiceRuntime<Self>) -> Self {
         let state = DoodleGameState::load(runtime.root_view_storage_context())
@@ -40,7 +40,16 @@ impl Service for DoodleGameService {
     async fn handle_query(&self, request: Request) -> Response {
         let room = self.state.room.get().clone();
         let current_word = self.state.current_word.get().clone();
+        let word_candidates = self.state.word_candidates.get().clone();
+        let word_packs = self.state.word_packs.get().clone();
         let archived_rooms = self.state.archived_rooms.get().clone();
+        let rejected_events = self.state.rejected_events.get().clone();
+        let pending_join = self.state.pending_join.get().clone();
+        let join_queue = self.state.join_queue.get().clone();
+        let listed_room = self.state.listed_room.get().clone();
+        let public_rooms = self.state.public_rooms.get().clone();
+        let match_ticket = self.state.match_ticket.get().clone();
+        let match_queue = self.state.match_queue.get().clone();
         
         let friends = self.state.friends.get().clone();
         let friend_requests_received = self.state.friend_requests_received.get().clone();
@@ -51,14 +60,25 @@ impl Service for DoodleGameService {
             QueryRoot {
                 room,
                 current_word,
+                word_candidates,
+                word_packs,
                 runtime: self.runtime.clone(),
                 archived_rooms,
+                rejected_events,
+                pending_join,
+                join_queue,
+                listed_room,
+                public_rooms,
+                match_ticket,
+                match_queue,
                 friends,
                 friend_requests_received,
                 friend_requests_sent,
                 room_invitations,
             },
             MutationRoot {
+                room: self.state.room.get().clone(),
+                word_candidates: self.state.word_candidates.get().clone(),
                 runtime: self.runtime.clone(),
             },
             EmptySubscription,
@@ -72,8 +92,17 @@ impl Service for DoodleGameService {
 struct QueryRoot {
     room: Option<doodle_game::GameRoom>,
     current_word: Option<String>,
+    word_candidates: Vec<String>,
+    word_packs: Vec<doodle_game::word_bank::WordBankSummary>,
     runtime: Arc<ServiceRuntime<DoodleGameService>>,
     archived_rooms: Vec<doodle_game::ArchivedRoom>,
+    rejected_events: Vec<doodle_game::RejectedEvents>,
+    pending_join: Option<doodle_game::PendingJoin>,
+    join_queue: Vec<doodle_game::JoinApplication>,
+    listed_room: Option<doodle_game::registry::RoomListing>,
+    public_rooms: Vec<doodle_game::registry::RoomListing>,
+    match_ticket: Option<doodle_game::registry::MatchTicket>,
+    match_queue: Vec<doodle_game::registry::MatchTicket>,
     
     // New fields
     friends: Vec<String>,
@@ -89,11 +118,95 @@ impl QueryRoot {
         self.room.as_ref()
     }
     
+    /// Get the join request this chain is waiting on (cleared once the room state arrives)
+    async fn pending_join(&self) -> Option<&doodle_game::PendingJoin> {
+        self.pending_join.as_ref()
+    }
+    
+    /// Get the join request status: "joining…", "rejected: room full", ... (null when not joining)
+    async fn join_status(&self) -> Option<String> {
+        self.pending_join.as_ref().map(|pending| pending.status())
+    }
+    
+    /// Get the join requests waiting for the host's approval (expired ones are left out)
+    async fn join_applications(&self) -> Vec<&doodle_game::JoinApplication> {
+        let now = self.runtime.system_time();
+        self.join_queue.iter().filter(|application| !application.is_expired(now)).collect()
+    }
+    
+    /// Get the chain that lists public rooms (null when the directory is disabled)
+    async fn registry_chain_id(&self) -> Option<ChainId> {
+        self.runtime.application_parameters().registry_chain_id
+    }
+    
+    /// Get our room's listing as last sent to the registry (host of a public room only)
+    async fn listed_room(&self) -> Option<&doodle_game::registry::RoomListing> {
+        self.listed_room.as_ref()
+    }
+    
+    /// Search the public rooms listed on this chain (registry chain only), most recently active first
+    /// `search` matches host name, language, word bank and category; stale listings are left out
+    async fn public_rooms(&self, search: Option<String>, offset: Option<u32>, limit: Option<u32>) -> doodle_game::registry::PublicRoomsPage {
+        let now = self.runtime.system_time();
+        doodle_game::registry::page(&self.public_rooms, search.as_deref(), now, offset.unwrap_or(0), limit)
+    }
+    
+    /// Get the quick-play request this chain is waiting on (cleared once matched or cancelled)
+    async fn match_ticket(&self) -> Option<&doodle_game::registry::MatchTicket> {
+        self.match_ticket.as_ref()
+    }
+    
+    /// Get the players waiting for a quick-play match (registry chain only)
+    async fn match_queue(&self) -> Vec<&doodle_game::registry::MatchTicket> {
+        let now = self.runtime.system_time();
+        self.match_queue.iter().filter(|ticket| !ticket.is_expired(now)).collect()
+    }
+    
     /// Get current word (only available on drawer's chain)
     async fn current_word(&self) -> Option<&String> {
         self.current_word.as_ref()
     }
     
+    /// Get the masked word hint for the current turn ("_c_ ___a_"), available on every chain
+    async fn masked_word(&self) -> Option<String> {
+        self.room.as_ref().and_then(|room| room.word_hint.as_ref().map(|hint| hint.mask.clone()))
+    }
+    
+    /// Get words offered for the current turn (only available on drawer's chain)
+    async fn word_candidates(&self) -> Vec<String> {
+        self.word_candidates.clone()
+    }
+    
+    /// List built-in word banks with their categories
+    async fn word_banks(&self) -> Vec<doodle_game::word_bank::WordBankSummary> {
+        doodle_game::word_bank::builtin_banks().iter().map(|bank| bank.summary()).collect()
+    }
+    
+    /// Get a built-in word bank with its full word list
+    async fn word_bank(&self, id: String) -> Option<doodle_game::word_bank::WordBank> {
+        doodle_game::word_bank::builtin_bank(&id)
+    }
+    
+    /// List custom word packs validated on this chain
+    async fn word_packs(&self) -> Vec<doodle_game::word_bank::WordBankSummary> {
+        self.word_packs.clone()
+    }
+    
+    /// Parse a custom word pack blob and return its first `limit` words (default 20)
+    /// Returns a GraphQL error describing why the pack is invalid
+    async fn word_pack_preview(&self, hash: String, limit: Option<i32>) -> async_graphql::Result<doodle_game::word_bank::WordBank> {
+        use linera_sdk::linera_base_types::{CryptoHash, DataBlobHash};
+        use std::str::FromStr;
+        
+        let crypto_hash = CryptoHash::from_str(&hash)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid word pack hash '{}': {:?}", hash, e)))?;
+        let bytes = self.runtime.read_data_blob(DataBlobHash(crypto_hash));
+        let mut pack = doodle_game::word_bank::parse_word_pack(&hash, &bytes)
+            .map_err(async_graphql::Error::new)?;
+        pack.words.truncate(limit.unwrap_or(20).max(0) as usize);
+        Ok(pack)
+    }
+    
     /// Get all players in the room
     async fn players(&self) -> Vec<doodle_game::Player> {
         self.room.as_ref().map_or(Vec::new(), |room| room.players.clone())
@@ -123,36 +236,41 @@ impl QueryRoot {
         self.room.as_ref().map_or(Vec::new(), |room| room.chat_messages.clone())
     }
     
+    /// Get audit results of finished turns (word commitment checks)
+    async fn turn_audits(&self) -> Vec<doodle_game::TurnAudit> {
+        self.room.as_ref().map_or(Vec::new(), |room| room.turn_audits.clone())
+    }
+    
     /// Get game progress info
     async fn game_progress(&self) -> Option<GameProgress> {
         self.room.as_ref().map(|room| GameProgress {
             current_round: room.current_round,
             total_rounds: room.total_rounds,
             seconds_per_round: room.seconds_per_round,
-            word_chosen_at: room.word_chosen_at.clone(),
+            word_chosen_at: room.word_chosen_at,
         })
     }
     
     /// Check if player has guessed correctly
-    async fn player_has_guessed(&self, player_name: String) -> bool {
+    async fn player_has_guessed(&self, chain_id: ChainId) -> bool {
         self.room.as_ref().map_or(false, |room| {
-            room.players.iter().any(|p| p.name == player_name && p.has_guessed)
+            room.players.iter().any(|p| p.chain_id == chain_id && p.has_guessed)
         })
     }
     
     /// Get player score
-    async fn player_score(&self, player_name: String) -> u32 {
+    async fn player_score(&self, chain_id: ChainId) -> u32 {
         self.room.as_ref().map_or(0, |room| {
             room.players.iter()
-                .find(|p| p.name == player_name)
+                .find(|p| p.chain_id == chain_id)
                 .map_or(0, |p| p.score)
         })
     }
     
     /// Check if specific player is current drawer
-    async fn is_player_drawer(&self, player_name: String) -> bool {
+    async fn is_player_drawer(&self, chain_id: ChainId) -> bool {
         self.room.as_ref().map_or(false, |room| {
-            room.get_current_drawer().map_or(false, |drawer| drawer.name == player_name)
+            room.get_current_drawer().map_or(false, |drawer| drawer.chain_id == chain_id)
         })
     }
     
@@ -183,6 +301,14 @@ impl QueryRoot {
         self.archived_rooms.clone()
     }
     
+    /// Get stream events this chain rejected because their source may not send them
+    async fn stream_diagnostics(&self) -> StreamDiagnostics {
+        StreamDiagnostics {
+            total_rejected: self.rejected_events.iter().map(|entry| entry.count).sum(),
+            rejected_events: self.rejected_events.clone(),
+        }
+    }
+    
     /// Get friends list
     async fn friends(&self) -> Vec<String> {
         self.friends.clone()
@@ -209,119 +335,245 @@ struct GameProgress {
     current_round: u32,
     total_rounds: u32,
     seconds_per_round: u32,
-    word_chosen_at: Option<String>,
+    word_chosen_at: Option<Timestamp>,
+}
+
+#[derive(async_graphql::SimpleObject)]
+struct StreamDiagnostics {
+    total_rejected: u64,
+    rejected_events: Vec<doodle_game::RejectedEvents>,
 }
 
 struct MutationRoot {
+    room: Option<doodle_game::GameRoom>,
+    word_candidates: Vec<String>,
     runtime: Arc<ServiceRuntime<DoodleGameService>>,
 }
 
+impl MutationRoot {
+    /// Custom packs are read and checked on-chain; built-in banks can be checked here
+    fn check_settings(settings: &doodle_game::RoomSettings) -> Result<(), doodle_game::DoodleError> {
+        if settings.word_pack.is_none() {
+            let bank = doodle_game::word_bank::builtin_bank(settings.word_bank_id())
+                .ok_or_else(|| doodle_game::DoodleError::InvalidSettings(format!("Unknown word bank '{}'", settings.word_bank_id())))?;
+            settings.validate(&bank).map_err(doodle_game::DoodleError::InvalidSettings)?;
+        }
+        Ok(())
+    }
+
+    /// Schedule the operation, or return the error the contract would reject it with
+    fn schedule(&self, operation: doodle_game::Operation) -> Result<(), doodle_game::DoodleError> {
+        operation.precheck(self.room.as_ref(), self.runtime.chain_id(), &self.word_candidates)?;
+        self.runtime.schedule_operation(&operation);
+        Ok(())
+    }
+}
+
 #[Object]
 impl MutationRoot {
     /// Create a new game room (host only)
-    async fn create_room(&self, host_name: String, avatar_json: Option<String>) -> String {
-        self.runtime.schedule_operation(&doodle_game::Operation::CreateRoom { host_name: host_name.clone(), avatar_json: avatar_json.unwrap_or_default() });
-        format!("Room created by host '{}'", host_name)
+    /// `password` is sent in the operation (readable in the block); the room state keeps only a hash salted with the room ID
+    async fn create_room(
+        &self,
+        host_name: String,
+        avatar_json: Option<String>,
+        settings: Option<doodle_game::RoomSettings>,
+        password: Option<String>,
+    ) -> async_graphql::Result<String> {
+        let settings = settings.unwrap_or_default();
+        Self::check_settings(&settings)?;
+        
+        self.schedule(doodle_game::Operation::CreateRoom {
+            host_name: host_name.clone(),
+            avatar_json: avatar_json.unwrap_or_default(),
+            settings,
+            password: password.filter(|password| !password.is_empty()),
+        })?;
+        Ok(format!("Room created by host '{}'", host_name))
+    }
+    
+    /// Change the room settings in the lobby (host only)
+    /// `password`: omit to keep the current one, empty string to remove it
+    async fn update_settings(&self, settings: doodle_game::RoomSettings, password: Option<String>) -> async_graphql::Result<String> {
+        Self::check_settings(&settings)?;
+        
+        self.schedule(doodle_game::Operation::UpdateSettings { settings, password })?;
+        Ok("Room settings update scheduled".to_string())
     }
     
     /// Join an existing room
-    async fn join_room(&self, host_chain_id: String, player_name: String, avatar_json: Option<String>) -> String {
-        self.runtime.schedule_operation(&doodle_game::Operation::JoinRoom { 
+    /// `password` travels to the host in plaintext and can be read in both chains' blocks
+    async fn join_room(&self, host_chain_id: String, player_name: String, avatar_json: Option<String>, password: Option<String>) -> async_graphql::Result<String> {
+        self.schedule(doodle_game::Operation::JoinRoom { 
             host_chain_id: host_chain_id.clone(), 
             player_name: player_name.clone(),
             avatar_json: avatar_json.unwrap_or_default(),
-        });
-        format!("Join request sent to host chain '{}' by player '{}'", host_chain_id, player_name)
+            password,
+        })?;
+        Ok(format!("Join request sent to host chain '{}' by player '{}'", host_chain_id, player_name))
     }
     
-    /// Start the game (host only)
-    async fn start_game(&self, rounds: i32, seconds_per_round: i32) -> String {
+    /// Start the game with the room's settings (host only)
+    async fn start_game(&self, rounds: i32, seconds_per_round: i32) -> async_graphql::Result<String> {
         let rounds = rounds as u32;
         let seconds_per_round = seconds_per_round as u32;
-        self.runtime.schedule_operation(&doodle_game::Operation::StartGame { rounds, seconds_per_round });
-        format!("Game started with {} rounds, {} seconds per round", rounds, seconds_per_round)
+        
+        self.schedule(doodle_game::Operation::StartGame {
+            rounds,
+            seconds_per_round,
+        })?;
+        Ok(format!("Game started with {} rounds, {} seconds per round", rounds, seconds_per_round))
     }
     
     /// Choose the next drawer (host only)
-    async fn choose_drawer(&self) -> String {
-        self.runtime.schedule_operation(&doodle_game::Operation::ChooseDrawer);
-        "Drawer selection initiated".to_string()
+    async fn choose_drawer(&self) -> async_graphql::Result<String> {
+        self.schedule(doodle_game::Operation::ChooseDrawer)?;
+        Ok("Drawer selection initiated".to_string())
+    }
+    
+    /// Enforce turn deadlines (anyone; forwarded to the host from player chains)
+    async fn tick(&self) -> async_graphql::Result<String> {
+        self.schedule(doodle_game::Operation::Tick)?;
+        Ok("Tick scheduled".to_string())
     }
     
     /// Choose a word to draw (drawer only)
-    async fn choose_word(&self, word: String) -> String {
-        self.runtime.schedule_operation(&doodle_game::Operation::ChooseWord { word: word.clone() });
-        format!("Word '{}' chosen", word)
+    /// `salt` must be a random string; it stays secret until the word is revealed at turn end
+    async fn choose_word(&self, word: String, salt: String) -> async_graphql::Result<String> {
+        self.schedule(doodle_game::Operation::ChooseWord { word: word.clone(), salt })?;
+        Ok(format!("Word '{}' chosen", word))
+    }
+    
+    /// Keep a secret salt for the word picked automatically if the drawer does not choose in time
+    /// (drawer only, call when the word candidates arrive)
+    async fn reserve_word_salt(&self, salt: String) -> async_graphql::Result<String> {
+        self.schedule(doodle_game::Operation::ReserveWordSalt { salt })?;
+        Ok("Word salt reserved".to_string())
+    }
+    
+    /// Publish the letter hint that is due now (drawer only, call periodically while drawing)
+    async fn publish_hint(&self) -> async_graphql::Result<String> {
+        self.schedule(doodle_game::Operation::PublishHint)?;
+        Ok("Hint publication scheduled".to_string())
     }
     
     /// Submit a guess for the current word
-    async fn guess_word(&self, guess: String) -> String {
-        self.runtime.schedule_operation(&doodle_game::Operation::GuessWord { 
+    async fn guess_word(&self, guess: String) -> async_graphql::Result<String> {
+        self.schedule(doodle_game::Operation::GuessWord { 
             guess: guess.clone() 
-        });
-        format!("Guess '{}' submitted", guess)
+        })?;
+        Ok(format!("Guess '{}' submitted", guess))
     }
-    
 
-    
     /// End the current match and completely delete the room (host only)
     /// WARNING: This permanently deletes the room and disconnects all players
-    async fn end_match(&self) -> String {
-        self.runtime.schedule_operation(&doodle_game::Operation::EndMatch);
-        "Room completely deleted and all players disconnected".to_string()
+    async fn end_match(&self) -> async_graphql::Result<String> {
+        self.schedule(doodle_game::Operation::EndMatch)?;
+        Ok("Room completely deleted and all players disconnected".to_string())
+    }
+
+    async fn leave_room(&self, blob_hashes: Option<Vec<String>>) -> async_graphql::Result<String> {
+        self.schedule(doodle_game::Operation::LeaveRoom { blob_hashes })?;
+        Ok("Leave room request scheduled".to_string())
+    }
+
+    /// Let a queued join request into the room (host only)
+    async fn approve_join(&self, player_chain_id: String) -> async_graphql::Result<String> {
+        self.schedule(doodle_game::Operation::ApproveJoin { player_chain_id: player_chain_id.clone() })?;
+        Ok(format!("Join request from '{}' approved", player_chain_id))
+    }
+
+    /// Turn down a queued join request (host only)
+    async fn reject_join(&self, player_chain_id: String) -> async_graphql::Result<String> {
+        self.schedule(doodle_game::Operation::RejectJoin { player_chain_id: player_chain_id.clone() })?;
+        Ok(format!("Join request from '{}' rejected", player_chain_id))
+    }
+
+    /// Ask the matchmaker for a game: an open public room, or a new one with other queued players
+    async fn queue_for_match(
+        &self,
+        player_name: String,
+        avatar_json: String,
+        preferences: Option<doodle_game::registry::MatchPreferences>,
+    ) -> async_graphql::Result<String> {
+        self.schedule(doodle_game::Operation::QueueForMatch {
+            player_name,
+            avatar_json,
+            preferences: preferences.unwrap_or_default(),
+        })?;
+        Ok("Queued for a match".to_string())
+    }
+
+    /// Stop waiting for a quick-play match
+    async fn leave_match_queue(&self) -> async_graphql::Result<String> {
+        self.schedule(doodle_game::Operation::LeaveMatchQueue)?;
+        Ok("Left the match queue".to_string())
     }
 
-    async fn leave_room(&self, blob_hashes: Option<Vec<String>>) -> String {
-        self.runtime.schedule_operation(&doodle_game::Operation::LeaveRoom { blob_hashes });
-        "Leave room request scheduled".to_string()
+    /// Remove a player from the room; they may join again (host only)
+    async fn kick_player(&self, player_chain_id: String) -> async_graphql::Result<String> {
+        self.schedule(doodle_game::Operation::KickPlayer { player_chain_id: player_chain_id.clone() })?;
+        Ok(format!("Player '{}' kicked", player_chain_id))
+    }
+
+    /// Remove a player and refuse their future join requests (host only)
+    async fn ban_player(&self, player_chain_id: String) -> async_graphql::Result<String> {
+        self.schedule(doodle_game::Operation::BanPlayer { player_chain_id: player_chain_id.clone() })?;
+        Ok(format!("Player '{}' banned", player_chain_id))
     }
 
     /// Schedule reading a data blob by its hash
     /// The hash should be a hex-encoded string of the blob hash (64 characters)
     /// Data blobs must be created externally via CLI `linera publish-data-blob` or GraphQL `publishDataBlob`
-    async fn read_data_blob(&self, hash: String) -> String {
-        self.runtime.schedule_operation(&doodle_game::Operation::ReadDataBlob { hash: hash.clone() });
-        format!("Data blob read scheduled for hash: {}", hash)
+    async fn read_data_blob(&self, hash: String) -> async_graphql::Result<String> {
+        self.schedule(doodle_game::Operation::ReadDataBlob { hash: hash.clone() })?;
+        Ok(format!("Data blob read scheduled for hash: {}", hash))
+    }
+
+    /// Validate a custom word pack blob and add it to this chain's pack list
+    async fn add_word_pack(&self, hash: String) -> async_graphql::Result<String> {
+        self.schedule(doodle_game::Operation::AddWordPack { hash: hash.clone() })?;
+        Ok(format!("Word pack validation scheduled for hash: {}", hash))
     }
 
     /// Request a friend (send request to target chain)
-    async fn request_friend(&self, target_chain_id: String) -> String {
-        self.runtime.schedule_operation(&doodle_game::Operation::RequestFriend { target_chain_id: target_chain_id.clone() });
-        format!("Friend request sent to '{}'", target_chain_id)
+    async fn request_friend(&self, target_chain_id: String) -> async_graphql::Result<String> {
+        self.schedule(doodle_game::Operation::RequestFriend { target_chain_id: target_chain_id.clone() })?;
+        Ok(format!("Friend request sent to '{}'", target_chain_id))
     }
     
     /// Accept a friend request
-    async fn accept_friend(&self, requester_chain_id: String) -> String {
-        self.runtime.schedule_operation(&doodle_game::Operation::AcceptFriend { requester_chain_id: requester_chain_id.clone() });
-        format!("Friend request from '{}' accepted", requester_chain_id)
+    async fn accept_friend(&self, requester_chain_id: String) -> async_graphql::Result<String> {
+        self.schedule(doodle_game::Operation::AcceptFriend { requester_chain_id: requester_chain_id.clone() })?;
+        Ok(format!("Friend request from '{}' accepted", requester_chain_id))
     }
     
     /// Decline a friend request
-    async fn decline_friend(&self, requester_chain_id: String) -> String {
-        self.runtime.schedule_operation(&doodle_game::Operation::DeclineFriend { requester_chain_id: requester_chain_id.clone() });
-        format!("Friend request from '{}' declined", requester_chain_id)
+    async fn decline_friend(&self, requester_chain_id: String) -> async_graphql::Result<String> {
+        self.schedule(doodle_game::Operation::DeclineFriend { requester_chain_id: requester_chain_id.clone() })?;
+        Ok(format!("Friend request from '{}' declined", requester_chain_id))
     }
     
     /// Invite a friend to the current room (must be host)
-    async fn invite_friend(&self, friend_chain_id: String) -> String {
-        self.runtime.schedule_operation(&doodle_game::Operation::InviteFriend { friend_chain_id: friend_chain_id.clone() });
-        format!("Invitation sent to '{}'", friend_chain_id)
+    async fn invite_friend(&self, friend_chain_id: String) -> async_graphql::Result<String> {
+        self.schedule(doodle_game::Operation::InviteFriend { friend_chain_id: friend_chain_id.clone() })?;
+        Ok(format!("Invitation sent to '{}'", friend_chain_id))
     }
     
     /// Accept a room invitation (checks validity and joins room)
-    async fn accept_invite(&self, host_chain_id: String, player_name: String, avatar_json: Option<String>) -> String {
-        self.runtime.schedule_operation(&doodle_game::Operation::AcceptInvite { 
+    async fn accept_invite(&self, host_chain_id: String, player_name: String, avatar_json: Option<String>) -> async_graphql::Result<String> {
+        self.schedule(doodle_game::Operation::AcceptInvite { 
             host_chain_id: host_chain_id.clone(),
             player_name: player_name.clone(),
             avatar_json: avatar_json.unwrap_or_default(),
-        });
-        format!("Invitation from '{}' accepted", host_chain_id)
+        })?;
+        Ok(format!("Invitation from '{}' accepted", host_chain_id))
     }
     
     /// Decline a room invitation
-    async fn decline_invite(&self, host_chain_id: String) -> String {
-        self.runtime.schedule_operation(&doodle_game::Operation::DeclineInvite { host_chain_id: host_chain_id.clone() });
-        format!("Invitation from '{}' declined", host_chain_id)
+    async fn decline_invite(&self, host_chain_id: String) -> async_graphql::Result<String> {
+        self.schedule(doodle_game::Operation::DeclineInvite { host_chain_id: host_chain_id.clone() })?;
+        Ok(format!("Invitation from '{}' declined", host_chain_id))
     }
 }
 
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use linera_sdk::views::{linera_views, RegisterView, RootView, ViewStorageContext};
-use doodle_game::{GameRoom, ArchivedRoom, Invitation};
+use doodle_game::{GameRoom, ArchivedRoom, GuessCheck, Invitation, JoinApplication, PendingJoin, RejectedEvents, registry::{MatchTicket, RoomListing}, word_bank::WordBankSummary};
 
 /// The application state for Doodle Game
 #[derive(RootView)]
@@ -10,11 +10,35 @@ use doodle_game::{GameRoom, ArchivedRoom, Invitation};
 pub struct DoodleGameState {
     // Game room data
     pub room: RegisterView<Option<GameRoom>>,
+    // Rooms created by this chain (numbers the room IDs)
+    pub rooms_created: RegisterView<u64>,
     // Current word (only stored on drawer's chain)
     pub current_word: RegisterView<Option<String>>,
+    // Salt of the current word commitment (only stored on drawer's chain, revealed at turn end)
+    pub word_salt: RegisterView<Option<String>>,
+    // Guesses this drawer's chain scored as correct this turn (only stored on drawer's chain, revealed at turn end)
+    pub correct_guesses: RegisterView<Vec<String>>,
+    // Drawer's hash of the current word (only stored on the host chain of host-validated rooms)
+    pub guess_check: RegisterView<Option<GuessCheck>>,
+    // Words offered to this chain for the current turn (only stored on drawer's chain)
+    pub word_candidates: RegisterView<Vec<String>>,
     // Host chain ID that player is subscribed to (to prevent duplicate subscriptions)
     // Only used by players
     pub subscribed_to_host: RegisterView<Option<String>>,
+    // Join requests waiting for the host's approval (only used by hosts of approval-required rooms)
+    pub join_queue: RegisterView<Vec<JoinApplication>>,
+    // Listing of our room the registry chain last received (only used by hosts of public rooms)
+    pub listed_room: RegisterView<Option<RoomListing>>,
+    // Quick-play request waiting for a match (only used by players)
+    pub match_ticket: RegisterView<Option<MatchTicket>>,
+    // Join request waiting for InitialStateSync or JoinRejected (only used by players)
+    pub pending_join: RegisterView<Option<PendingJoin>>,
+    // This player's guesses reported as close during the current turn (private feedback)
+    pub close_guesses: RegisterView<Vec<String>>,
+    // Custom word packs (data blobs) validated on this chain
+    pub word_packs: RegisterView<Vec<WordBankSummary>>,
+    // Stream events rejected by the authority policy, per event and source chain
+    pub rejected_events: RegisterView<Vec<RejectedEvents>>,
     // Archived rooms history (for storing data after deletion)
     pub archived_rooms: RegisterView<Vec<ArchivedRoom>>,
     
@@ -26,4 +50,9 @@ pub struct DoodleGameState {
     // Invite System
     pub room_invitations: RegisterView<Vec<Invitation>>,
     pub sent_invitations: RegisterView<Vec<String>>, // Track sent invites to clear them on game start
+    
+    // Room Directory (only used by the registry chain)
+    pub public_rooms: RegisterView<Vec<RoomListing>>,
+    // Players waiting for a quick-play match
+    pub match_queue: RegisterView<Vec<MatchTicket>>,
 }
\ No newline at end of file
@@ -0,0 +1,432 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+/*! Word banks offered to the drawer: built-in banks and custom packs published as data blobs */
+
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::guess;
+
+// Bank used when the host does not pick one
+pub const DEFAULT_WORD_BANK: &str = "classic_en";
+// Number of candidates offered to the drawer when the host does not pick
+pub const DEFAULT_WORD_CHOICES: u32 = 3;
+pub const MAX_WORD_CHOICES: u32 = 5;
+// Shorter words are never offered ("a", "ox", ...)
+pub const MIN_WORD_LENGTH: usize = 3;
+pub const MAX_WORD_LENGTH: usize = 32;
+
+// Custom word pack limits
+pub const WORD_PACK_HEADER: &str = "doodle-word-pack v1";
+pub const MAX_WORD_PACK_BYTES: usize = 64 * 1024;
+pub const MAX_WORD_PACK_WORDS: usize = 2_000;
+pub const MIN_WORD_PACK_WORDS: usize = MAX_WORD_CHOICES as usize;
+pub const DEFAULT_PACK_CATEGORY: &str = "custom";
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, async_graphql::Enum, PartialEq, Eq)]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    pub fn from_label(label: &str) -> Option<Self> {
+        match label.to_lowercase().as_str() {
+            "easy" => Some(Difficulty::Easy),
+            "medium" => Some(Difficulty::Medium),
+            "hard" => Some(Difficulty::Hard),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, async_graphql::SimpleObject)]
+#[graphql(rename_fields = "camelCase")]
+pub struct WordEntry {
+    pub word: String,
+    pub category: String,
+    pub language: String,
+    pub difficulty: Difficulty,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, async_graphql::SimpleObject)]
+#[graphql(rename_fields = "camelCase")]
+pub struct WordBank {
+    pub id: String,
+    pub name: String,
+    pub language: String,
+    pub words: Vec<WordEntry>,
+}
+
+// Bank description for listings (without the word list)
+#[derive(Debug, Clone, Serialize, Deserialize, async_graphql::SimpleObject)]
+#[graphql(rename_fields = "camelCase")]
+pub struct WordBankSummary {
+    pub id: String,
+    pub name: String,
+    pub language: String,
+    pub categories: Vec<String>,
+    pub word_count: u32,
+}
+
+// (word, category, difficulty)
+const CLASSIC_EN: &[(&str, &str, Difficulty)] = &[
+    ("cat", "animals", Difficulty::Easy),
+    ("dog", "animals", Difficulty::Easy),
+    ("fish", "animals", Difficulty::Easy),
+    ("horse", "animals", Difficulty::Easy),
+    ("giraffe", "animals", Difficulty::Medium),
+    ("penguin", "animals", Difficulty::Medium),
+    ("octopus", "animals", Difficulty::Medium),
+    ("chameleon", "animals", Difficulty::Hard),
+    ("platypus", "animals", Difficulty::Hard),
+    ("apple", "food", Difficulty::Easy),
+    ("banana", "food", Difficulty::Easy),
+    ("pizza", "food", Difficulty::Easy),
+    ("ice cream", "food", Difficulty::Medium),
+    ("sandwich", "food", Difficulty::Medium),
+    ("pancake", "food", Difficulty::Medium),
+    ("sushi", "food", Difficulty::Hard),
+    ("lasagna", "food", Difficulty::Hard),
+    ("house", "objects", Difficulty::Easy),
+    ("chair", "objects", Difficulty::Easy),
+    ("clock", "objects", Difficulty::Easy),
+    ("umbrella", "objects", Difficulty::Medium),
+    ("scissors", "objects", Difficulty::Medium),
+    ("telescope", "objects", Difficulty::Hard),
+    ("hourglass", "objects", Difficulty::Hard),
+    ("tree", "nature", Difficulty::Easy),
+    ("sun", "nature", Difficulty::Easy),
+    ("flower", "nature", Difficulty::Easy),
+    ("volcano", "nature", Difficulty::Medium),
+    ("rainbow", "nature", Difficulty::Medium),
+    ("waterfall", "nature", Difficulty::Hard),
+    ("glacier", "nature", Difficulty::Hard),
+    ("swimming", "actions", Difficulty::Medium),
+    ("dancing", "actions", Difficulty::Medium),
+    ("sleeping", "actions", Difficulty::Medium),
+    ("juggling", "actions", Difficulty::Hard),
+    ("skydiving", "actions", Difficulty::Hard),
+];
+
+const CLASSIC_UK: &[(&str, &str, Difficulty)] = &[
+    ("кіт", "тварини", Difficulty::Easy),
+    ("пес", "тварини", Difficulty::Easy),
+    ("риба", "тварини", Difficulty::Easy),
+    ("жираф", "тварини", Difficulty::Medium),
+    ("пінгвін", "тварини", Difficulty::Medium),
+    ("восьминіг", "тварини", Difficulty::Hard),
+    ("яблуко", "їжа", Difficulty::Easy),
+    ("банан", "їжа", Difficulty::Easy),
+    ("піца", "їжа", Difficulty::Easy),
+    ("вареники", "їжа", Difficulty::Medium),
+    ("морозиво", "їжа", Difficulty::Medium),
+    ("будинок", "предмети", Difficulty::Easy),
+    ("стілець", "предмети", Difficulty::Easy),
+    ("годинник", "предмети", Difficulty::Medium),
+    ("парасолька", "предмети", Difficulty::Medium),
+    ("телескоп", "предмети", Difficulty::Hard),
+    ("дерево", "природа", Difficulty::Easy),
+    ("сонце", "природа", Difficulty::Easy),
+    ("вулкан", "природа", Difficulty::Medium),
+    ("веселка", "природа", Difficulty::Medium),
+    ("водоспад", "природа", Difficulty::Hard),
+];
+
+fn build_bank(id: &str, name: &str, language: &str, words: &[(&str, &str, Difficulty)]) -> WordBank {
+    WordBank {
+        id: id.to_string(),
+        name: name.to_string(),
+        language: language.to_string(),
+        words: words.iter()
+            .filter(|(word, _, _)| word.chars().count() >= MIN_WORD_LENGTH)
+            .map(|(word, category, difficulty)| WordEntry {
+                word: word.to_string(),
+                category: category.to_string(),
+                language: language.to_string(),
+                difficulty: *difficulty,
+            })
+            .collect(),
+    }
+}
+
+/// All word banks compiled into the application
+pub fn builtin_banks() -> Vec<WordBank> {
+    vec![
+        build_bank("classic_en", "Classic (English)", "en", CLASSIC_EN),
+        build_bank("classic_uk", "Класичний (Українська)", "uk", CLASSIC_UK),
+    ]
+}
+
+/// Look up a built-in bank by id
+pub fn builtin_bank(id: &str) -> Option<WordBank> {
+    builtin_banks().into_iter().find(|bank| bank.id == id)
+}
+
+/// Parse and validate a custom word pack published as a data blob
+///
+/// The pack is UTF-8 text: the header line, `key: value` headers, a blank line,
+/// then one word per line with optional category and difficulty:
+///
+/// ```text
+/// doodle-word-pack v1
+/// name: Movie night
+/// language: en
+///
+/// # word | category | difficulty
+/// popcorn | snacks | easy
+/// director's chair | props
+/// clapperboard
+/// ```
+pub fn parse_word_pack(id: &str, bytes: &[u8]) -> Result<WordBank, String> {
+    if bytes.len() > MAX_WORD_PACK_BYTES {
+        return Err(format!("Word pack is {} bytes, limit is {}", bytes.len(), MAX_WORD_PACK_BYTES));
+    }
+    let text = std::str::from_utf8(bytes).map_err(|_| "Word pack is not valid UTF-8".to_string())?;
+
+    let mut lines = text.lines();
+    if lines.next().map(str::trim) != Some(WORD_PACK_HEADER) {
+        return Err(format!("Word pack must start with '{}'", WORD_PACK_HEADER));
+    }
+
+    let mut name = None;
+    let mut language = None;
+    let mut in_header = true;
+    let mut seen = BTreeSet::new();
+    let mut words = Vec::new();
+
+    for (index, raw_line) in lines.enumerate() {
+        let line_number = index + 2;
+        let line = raw_line.trim();
+        if line.starts_with('#') {
+            continue;
+        }
+
+        if in_header {
+            if line.is_empty() {
+                in_header = false;
+                continue;
+            }
+            let (key, value) = line.split_once(':')
+                .ok_or_else(|| format!("Line {}: expected 'key: value' header", line_number))?;
+            match key.trim() {
+                "name" => name = Some(value.trim().to_string()),
+                "language" => language = Some(value.trim().to_lowercase()),
+                other => return Err(format!("Line {}: unknown header '{}'", line_number, other)),
+            }
+            continue;
+        }
+
+        if line.is_empty() {
+            continue;
+        }
+
+        let mut fields = line.split('|').map(str::trim);
+        let word = fields.next().unwrap_or_default();
+        let category = fields.next().filter(|c| !c.is_empty()).unwrap_or(DEFAULT_PACK_CATEGORY);
+        let difficulty = match fields.next().filter(|d| !d.is_empty()) {
+            Some(label) => Difficulty::from_label(label)
+                .ok_or_else(|| format!("Line {}: unknown difficulty '{}'", line_number, label))?,
+            None => Difficulty::Medium,
+        };
+        if fields.next().is_some() {
+            return Err(format!("Line {}: expected 'word | category | difficulty'", line_number));
+        }
+
+        // Words are guessed by their match key, so that is what must be long enough and unique
+        // ("Ice-cream" and "ice cream" are the same word, "!!!" can never be guessed)
+        let key = guess::match_key(word);
+        if word.chars().count() > MAX_WORD_LENGTH || key.chars().count() < MIN_WORD_LENGTH {
+            return Err(format!(
+                "Line {}: '{}' must be {}-{} characters with at least {} letters or digits",
+                line_number, word, MIN_WORD_LENGTH, MAX_WORD_LENGTH, MIN_WORD_LENGTH
+            ));
+        }
+        if !seen.insert(key) {
+            return Err(format!("Line {}: duplicate word '{}'", line_number, word));
+        }
+        if words.len() >= MAX_WORD_PACK_WORDS {
+            return Err(format!("Word pack has more than {} words", MAX_WORD_PACK_WORDS));
+        }
+
+        words.push(WordEntry {
+            word: word.to_string(),
+            category: category.to_string(),
+            language: String::new(),
+            difficulty,
+        });
+    }
+
+    let language = language.ok_or_else(|| "Word pack header is missing 'language'".to_string())?;
+    if words.len() < MIN_WORD_PACK_WORDS {
+        return Err(format!("Word pack needs at least {} words, found {}", MIN_WORD_PACK_WORDS, words.len()));
+    }
+    for entry in &mut words {
+        entry.language = language.clone();
+    }
+
+    Ok(WordBank {
+        id: id.to_string(),
+        name: name.unwrap_or_else(|| "Custom pack".to_string()),
+        language,
+        words,
+    })
+}
+
+impl WordBank {
+    pub fn summary(&self) -> WordBankSummary {
+        WordBankSummary {
+            id: self.id.clone(),
+            name: self.name.clone(),
+            language: self.language.clone(),
+            categories: self.categories(),
+            word_count: self.words.len() as u32,
+        }
+    }
+
+    /// Distinct categories in bank order
+    pub fn categories(&self) -> Vec<String> {
+        let mut categories: Vec<String> = Vec::new();
+        for entry in &self.words {
+            if !categories.contains(&entry.category) {
+                categories.push(entry.category.clone());
+            }
+        }
+        categories
+    }
+
+    /// Words matching the category/difficulty filters (None = any)
+    pub fn filtered(&self, category: Option<&str>, difficulty: Option<Difficulty>) -> Vec<&WordEntry> {
+        self.words.iter()
+            .filter(|entry| category.map_or(true, |c| entry.category == c))
+            .filter(|entry| difficulty.map_or(true, |d| entry.difficulty == d))
+            .collect()
+    }
+
+    /// Deterministically pick up to `count` distinct candidates for the drawer
+    /// A filter that leaves fewer words offers fewer; only one that matches nothing falls back to the whole bank
+    /// (RoomSettings::validate refuses such filters, but a custom pack may have changed since)
+    pub fn pick_candidates(&self, category: Option<&str>, difficulty: Option<Difficulty>, count: u32, seed: &str) -> Vec<String> {
+        let mut pool = self.filtered(category, difficulty);
+        if pool.is_empty() {
+            pool = self.words.iter().collect();
+        }
+
+        let mut state = seed_from(seed);
+        let mut candidates = Vec::new();
+        while candidates.len() < count as usize && !pool.is_empty() {
+            let index = (next_random(&mut state) % pool.len() as u64) as usize;
+            candidates.push(pool.swap_remove(index).word.clone());
+        }
+        candidates
+    }
+}
+
+// FNV-1a hash of the seed string
+pub(crate) fn seed_from(seed: &str) -> u64 {
+    seed.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+// SplitMix64 step
+pub(crate) fn next_random(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Valid pack header followed by `words`, one per line
+    fn pack(words: &[String]) -> String {
+        format!("{}\nname: Test\nlanguage: EN\n\n{}\n", WORD_PACK_HEADER, words.join("\n"))
+    }
+
+    fn numbered(count: usize) -> Vec<String> {
+        (0..count).map(|index| format!("word{:04}", index)).collect()
+    }
+
+    #[test]
+    fn parses_a_valid_pack() {
+        let mut words = numbered(MIN_WORD_PACK_WORDS);
+        words[0] = "rocket | space | hard".to_string();
+        let bank = parse_word_pack("hash", pack(&words).as_bytes()).unwrap();
+        assert_eq!((bank.name.as_str(), bank.language.as_str(), bank.words.len()), ("Test", "en", MIN_WORD_PACK_WORDS));
+        assert_eq!((bank.words[0].category.as_str(), bank.words[0].difficulty), ("space", Difficulty::Hard));
+        assert_eq!((bank.words[1].category.as_str(), bank.words[1].difficulty), (DEFAULT_PACK_CATEGORY, Difficulty::Medium));
+    }
+
+    #[test]
+    fn candidates_stay_within_the_filters() {
+        let bank = builtin_bank(DEFAULT_WORD_BANK).unwrap();
+        // Two hard food words: both are offered, never words from elsewhere in the bank
+        let mut candidates = bank.pick_candidates(Some("food"), Some(Difficulty::Hard), MAX_WORD_CHOICES, "seed");
+        candidates.sort();
+        assert_eq!(candidates, vec!["lasagna".to_string(), "sushi".to_string()]);
+
+        let candidates = bank.pick_candidates(Some("food"), None, 3, "seed");
+        assert_eq!(candidates.len(), 3);
+        assert!(candidates.iter().all(|word| bank.filtered(Some("food"), None).iter().any(|entry| &entry.word == word)));
+
+        // Nothing matches: offer from the whole bank rather than nothing
+        assert_eq!(bank.pick_candidates(Some("actions"), Some(Difficulty::Easy), 3, "seed").len(), 3);
+    }
+
+    #[test]
+    fn rejects_oversized_packs() {
+        let bytes = vec![b'a'; MAX_WORD_PACK_BYTES + 1];
+        assert!(parse_word_pack("hash", &bytes).unwrap_err().contains("limit"));
+        assert!(parse_word_pack("hash", pack(&numbered(MAX_WORD_PACK_WORDS + 1)).as_bytes()).unwrap_err().contains("more than"));
+    }
+
+    #[test]
+    fn rejects_too_few_or_duplicate_words() {
+        let few = pack(&numbered(MIN_WORD_PACK_WORDS - 1));
+        assert!(parse_word_pack("hash", few.as_bytes()).unwrap_err().contains("at least"));
+
+        let with = |extra: &[&str]| {
+            let mut words = numbered(MIN_WORD_PACK_WORDS);
+            words.extend(extra.iter().map(|word| word.to_string()));
+            parse_word_pack("hash", pack(&words).as_bytes())
+        };
+        assert!(with(&["WORD0000"]).unwrap_err().contains("duplicate"));
+        // Spelled differently, guessed the same
+        assert!(with(&["ice cream", "Ice-Cream"]).unwrap_err().contains("duplicate"));
+        assert!(with(&["crème brûlée", "creme brulee"]).unwrap_err().contains("duplicate"));
+        assert!(with(&["ice cream", "iced cream"]).is_ok());
+    }
+
+    #[test]
+    fn rejects_malformed_packs() {
+        let words = numbered(MIN_WORD_PACK_WORDS);
+        let body = words.join("\n");
+        let invalid = |text: String| parse_word_pack("hash", text.as_bytes()).unwrap_err();
+
+        assert!(invalid(format!("not a pack\n\n{}", body)).contains("must start with"));
+        assert!(parse_word_pack("hash", &[0xff, 0xfe]).unwrap_err().contains("UTF-8"));
+        assert!(invalid(format!("{}\nname: Test\n\n{}", WORD_PACK_HEADER, body)).contains("missing 'language'"));
+        assert!(invalid(format!("{}\nlanguage en\n\n{}", WORD_PACK_HEADER, body)).contains("key: value"));
+        assert!(invalid(format!("{}\nauthor: me\n\n{}", WORD_PACK_HEADER, body)).contains("unknown header"));
+
+        let with = |line: &str| {
+            let mut words = words.clone();
+            words.push(line.to_string());
+            invalid(pack(&words))
+        };
+        assert!(with("ox").contains("characters"));
+        assert!(with(&"x".repeat(MAX_WORD_LENGTH + 1)).contains("characters"));
+        // Long enough, but nothing left to guess once punctuation is dropped
+        assert!(with("!!!").contains("letters or digits"));
+        assert!(with("a-b").contains("letters or digits"));
+        assert!(with("rocket | space | impossible").contains("unknown difficulty"));
+        assert!(with("rocket | space | hard | extra").contains("expected 'word | category | difficulty'"));
+    }
+}
@@ -27,14 +27,21 @@ export interface ChatMessage {
   timestamp: number;
 }
 
+// Secret salt for a word commitment (16 random bytes, hex)
+function randomSalt(): string {
+  const saltBytes = crypto.getRandomValues(new Uint8Array(16));
+  return Array.from(saltBytes, (b) => b.toString(16).padStart(2, "0")).join("");
+}
+
+// Mirrors HINT_SCHEDULE_PERCENT in sc/src/hint.rs
+const HINT_SCHEDULE_PERCENT = [40, 60, 80];
+
 const WORDS = [
   "cat", "dog", "house", "tree", "sun", "cloud", "star",
   "flower", "car", "bicycle", "airplane", "ship", "mountain",
   "river", "sea", "forest", "city", "street", "park", "school"
 ];
 
-const FIXED_WORD_OPTIONS = ["cat", "house", "tree"];
-
 interface GameProps {
   playerName: string;
   hostChainId: string;
@@ -54,6 +61,7 @@ export function Game({ playerName, hostChainId, settings, onGameEnd, onBackToLob
   const [round, setRound] = useState<number>(1);
   const [showWordSelector, setShowWordSelector] = useState<boolean>(false);
   const [wordOptions, setWordOptions] = useState<string[]>([]);
+  const [wordError, setWordError] = useState<string>("");
   const [canvasData, setCanvasData] = useState<string>("");
   const canvasCompRef = useRef<CanvasHandle | null>(null);
   const wordOptionsRef = useRef<string[]>([]);
@@ -76,6 +84,7 @@ export function Game({ playerName, hostChainId, settings, onGameEnd, onBackToLob
   const lastBlobPublishRef = useRef<Promise<string> | null>(null);
   const sentGameEndRef = useRef(false);
   const sentWordForTurnRef = useRef<string | null>(null);
+  const reservedSaltForTurnRef = useRef<string | null>(null);
   const systemNoticeRef = useRef<{ turnKey: string | null; fired: Record<string, boolean> }>({ turnKey: null, fired: {} });
   const messageTimesRef = useRef<Record<string, number>>({});
 
@@ -153,7 +162,7 @@ export function Game({ playerName, hostChainId, settings, onGameEnd, onBackToLob
     messageTimesRef.current = {};
   };
 
-  const applyRoomState = (room: any, currentWordFromQuery?: any) => {
+  const applyRoomState = (room: any, currentWordFromQuery?: any, wordCandidatesFromQuery?: any) => {
     roomRef.current = room;
 
     const mappedPlayers: Player[] = (room.players ?? []).map((p: any, idx: number) => ({
@@ -176,8 +185,11 @@ export function Game({ playerName, hostChainId, settings, onGameEnd, onBackToLob
       room.drawerChosenAt &&
       !room.wordChosenAt;
     setShowWordSelector(Boolean(awaitingWord && amIDrawer));
-    if (awaitingWord && amIDrawer && wordOptionsRef.current.length === 0) {
-      setWordOptions(FIXED_WORD_OPTIONS);
+    const offered: string[] = Array.isArray(wordCandidatesFromQuery) ? wordCandidatesFromQuery.map(String) : [];
+    // Only the contract's candidates can be chosen; until they arrive the selector waits
+    if (awaitingWord && amIDrawer && offered.length > 0 && offered.join("|") !== wordOptionsRef.current.join("|")) {
+      setWordOptions(offered);
+      setWordError("");
     }
     if (!amIDrawer || awaitingWord || !isDrawingPhase) {
       setCurrentWord("");
@@ -197,7 +209,7 @@ export function Game({ playerName, hostChainId, settings, onGameEnd, onBackToLob
         const ts = typeof existing === "number" ? existing : (messageTimesRef.current[id] = baseTs + idx);
         return {
           id,
-          playerId: String(m.playerName ?? `p-${idx}`),
+          playerId: String(m.playerChainId || m.playerName || `p-${idx}`),
           playerName: String(m.playerName ?? "Player"),
           message: String(m.message ?? ""),
           isCorrect: !!m.isCorrectGuess,
@@ -228,7 +240,7 @@ export function Game({ playerName, hostChainId, settings, onGameEnd, onBackToLob
     try {
       const startedAt = typeof performance !== "undefined" ? performance.now() : Date.now();
       const roomResponse = await application.query(
-        '{ "query": "query { currentWord room { hostChainId players { chainId name avatarJson score hasGuessed status } gameState currentRound totalRounds secondsPerRound currentDrawerIndex wordChosenAt drawerChosenAt blobHashes chatMessages { playerName message isCorrectGuess pointsAwarded } } }" }'
+        '{ "query": "query { currentWord wordCandidates room { hostChainId players { chainId name avatarJson score hasGuessed status } gameState currentRound totalRounds secondsPerRound currentDrawerIndex wordChosenAt drawerChosenAt blobHashes chatMessages { playerChainId playerName message isCorrectGuess pointsAwarded } } }" }'
       );
       const endedAt = typeof performance !== "undefined" ? performance.now() : Date.now();
       try {
@@ -239,6 +251,7 @@ export function Game({ playerName, hostChainId, settings, onGameEnd, onBackToLob
       const roomData = JSON.parse(roomResponse);
       const room = roomData.data?.room;
       const currentWordFromQuery = roomData.data?.currentWord;
+      const wordCandidatesFromQuery = roomData.data?.wordCandidates;
       const matchesHost = room?.hostChainId && String(room.hostChainId).trim() === String(hostChainId).trim();
       if (!room || !matchesHost) {
         if (roomRef.current) {
@@ -249,19 +262,20 @@ export function Game({ playerName, hostChainId, settings, onGameEnd, onBackToLob
               if (!application || !ready) return;
               try {
                 const retry = await application.query(
-                  '{ "query": "query { currentWord room { hostChainId players { chainId name avatarJson score hasGuessed status } gameState currentRound totalRounds secondsPerRound currentDrawerIndex wordChosenAt drawerChosenAt blobHashes chatMessages { playerName message isCorrectGuess pointsAwarded } } }" }'
+                  '{ "query": "query { currentWord wordCandidates room { hostChainId players { chainId name avatarJson score hasGuessed status } gameState currentRound totalRounds secondsPerRound currentDrawerIndex wordChosenAt drawerChosenAt blobHashes chatMessages { playerChainId playerName message isCorrectGuess pointsAwarded } } }" }'
                 );
                 if (!aliveRef.current) return;
                 const parsed = JSON.parse(retry);
                 const roomAfter = parsed?.data?.room;
                 const currentWordAfter = parsed?.data?.currentWord;
+                const wordCandidatesAfter = parsed?.data?.wordCandidates;
                 const matchesAfter = roomAfter?.hostChainId && String(roomAfter.hostChainId).trim() === String(hostChainId).trim();
                 if (!roomAfter || !matchesAfter) {
                   cleanupRoomSession();
                   if (aliveRef.current) onBackToLobby();
                   return;
                 }
-                applyRoomState(roomAfter, currentWordAfter);
+                applyRoomState(roomAfter, currentWordAfter, wordCandidatesAfter);
               } catch {}
             }, 2000);
           }
@@ -272,7 +286,7 @@ export function Game({ playerName, hostChainId, settings, onGameEnd, onBackToLob
         clearTimeout(pendingBackToLobbyTimeoutRef.current);
         pendingBackToLobbyTimeoutRef.current = null;
       }
-      applyRoomState(room, currentWordFromQuery);
+      applyRoomState(room, currentWordFromQuery, wordCandidatesFromQuery);
     } catch { }
   };
 
@@ -350,8 +364,8 @@ export function Game({ playerName, hostChainId, settings, onGameEnd, onBackToLob
       autoWordTimeoutRef.current = null;
     }
     autoWordTimeoutRef.current = window.setTimeout(() => {
-      if (!roomRef.current?.wordChosenAt) {
-        const choice = (wordOptions.length ? wordOptions : FIXED_WORD_OPTIONS)[0];
+      const choice = wordOptions[0];
+      if (!roomRef.current?.wordChosenAt && choice) {
         handleChooseWord(choice);
       }
     }, remainingMs);
@@ -402,6 +416,35 @@ export function Game({ playerName, hostChainId, settings, onGameEnd, onBackToLob
     }
   }, [players, timeLeft]);
 
+  // Publish letter hints on the contract's schedule (drawer only): 40%, 60% and 80% of the drawing time
+  useEffect(() => {
+    const room = roomRef.current;
+    if (!room || !application || !ready) return;
+    const amIDrawer = players[(room.currentDrawerIndex ?? -1)]?.id === chainId;
+    const isDrawingPhase = room.gameState === 'Drawing' || room.gameState === 'DRAWING';
+    if (!amIDrawer || !isDrawingPhase || !room.wordChosenAt) return;
+
+    const nowSec = Date.now() / 1000;
+    const wordChosenSec = parseInt(room.wordChosenAt) / 1000000;
+    const elapsed = Math.max(0, nowSec - wordChosenSec);
+    const duration = room.secondsPerRound ?? settings.roundTime;
+    const scheduledWordChosenAt = room.wordChosenAt;
+    const timeoutIds = HINT_SCHEDULE_PERCENT
+      .map((percent) => duration * percent / 100 - elapsed)
+      .filter((delay) => delay > 0)
+      .map((delay) => window.setTimeout(async () => {
+        const latest = roomRef.current;
+        if (!latest || latest.wordChosenAt !== scheduledWordChosenAt) return;
+        try {
+          await application.query('{ "query": "mutation { publishHint }" }');
+        } catch { }
+      }, delay * 1000 + 500));
+
+    return () => {
+      timeoutIds.forEach((id) => clearTimeout(id));
+    };
+  }, [players, application, ready]);
+
   // Reset chooseDrawer in-flight when state transitions
   useEffect(() => {
     const room = roomRef.current;
@@ -414,12 +457,32 @@ export function Game({ playerName, hostChainId, settings, onGameEnd, onBackToLob
   const handleChooseWord = async (word: string) => {
     if (!application || !ready) return;
     try {
-      await application.query('{ "query": "mutation { chooseWord(word: \\\"' + word + '\\\") }" }');
+      const salt = randomSalt();
+      const response = await application.query('{ "query": "mutation { chooseWord(word: \\\"' + word + '\\\", salt: \\\"' + salt + '\\\") }" }');
+      const errors = JSON.parse(response)?.errors;
+      if (Array.isArray(errors) && errors.length > 0) {
+        throw new Error(String(errors[0]?.message ?? "Word was rejected"));
+      }
+      setWordError("");
       setShowWordSelector(false);
       setCurrentWord(word);
-    } catch { }
+    } catch (error) {
+      setWordError(error instanceof Error ? error.message : String(error));
+    }
   };
 
+  // Reserve a secret salt as soon as the candidates arrive, so an auto-picked word is committed safely
+  useEffect(() => {
+    const room = roomRef.current;
+    if (!room || !application || !ready || wordOptions.length === 0) return;
+    const amIDrawer = players[(room.currentDrawerIndex ?? -1)]?.id === chainId;
+    const awaitingWord = (room.gameState === 'WaitingForWord' || room.gameState === 'WAITING_FOR_WORD') && room.drawerChosenAt && !room.wordChosenAt;
+    const turnId = String(room.drawerChosenAt ?? "");
+    if (!amIDrawer || !awaitingWord || reservedSaltForTurnRef.current === turnId) return;
+    reservedSaltForTurnRef.current = turnId;
+    application.query('{ "query": "mutation { reserveWordSalt(salt: \\\"' + randomSalt() + '\\\") }" }').catch(() => { });
+  }, [wordOptions, application, ready]);
+
   useEffect(() => {
     const room = roomRef.current;
     if (!room) return;
@@ -706,6 +769,7 @@ export function Game({ playerName, hostChainId, settings, onGameEnd, onBackToLob
               {showWordSelector && isDrawing && (
                 <WordSelector
                   words={wordOptions}
+                  error={wordError}
                   onSelect={handleChooseWord}
                 />
               )}
@@ -8,7 +8,7 @@ interface GlobalDebugOverlayProps {
 }
 
 const ROOM_QUERY = 'query { room { hostChainId gameState totalRounds secondsPerRound players { chainId name avatarJson status } } }';
-const GAME_QUERY = 'query { room { hostChainId players { chainId name avatarJson score hasGuessed status } gameState currentRound totalRounds secondsPerRound currentDrawerIndex wordChosenAt drawerChosenAt chatMessages { playerName message isCorrectGuess pointsAwarded } } }';
+const GAME_QUERY = 'query { room { hostChainId players { chainId name avatarJson score hasGuessed status } gameState currentRound totalRounds secondsPerRound currentDrawerIndex wordChosenAt drawerChosenAt chatMessages { playerChainId playerName message isCorrectGuess pointsAwarded } } }';
 
 export function GlobalDebugOverlay({ application, client, ready }: GlobalDebugOverlayProps) {
   const [open, setOpen] = useState(false);
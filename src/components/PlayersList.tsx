@@ -22,7 +22,9 @@ export function PlayersList({ players, localPlayerId, className }: PlayersListPr
 
       <div className="divide-y-2 divide-black">
         {sortedPlayers.map((player) => {
-          const isLeft = String(player.status || "").toLowerCase() === "left";
+          const status = String(player.status || "").toLowerCase();
+          const isKicked = status === "kicked";
+          const isLeft = status === "left" || isKicked;
           return (
           <div
             key={player.id}
@@ -45,7 +47,7 @@ export function PlayersList({ players, localPlayerId, className }: PlayersListPr
             <div className="flex-1 min-w-0">
               <div className="truncate">
                 {player.name}
-                {isLeft ? " (left)" : ""}
+                {isKicked ? " (kicked)" : isLeft ? " (left)" : ""}
               </div>
             </div>
 
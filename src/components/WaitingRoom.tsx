@@ -8,6 +8,9 @@ import { FriendsDialog } from "./FriendsDialog";
 import { CharacterAvatar } from "./CharacterAvatar";
 import { getCharacterIdForPlayer, getCharacterPropsById, getSelectedAvatarJson, parseAvatarJson } from "../utils/characters";
 
+// Half of HEARTBEAT_SECONDS in sc/src/registry.rs
+const LOBBY_HEARTBEAT_MS = 60_000;
+
 interface WaitingRoomProps {
   hostChainId: string;
   playerName: string;
@@ -25,6 +28,7 @@ export function WaitingRoom({ hostChainId, playerName, isHost, onStartGame, onBa
   const [copied, setCopied] = useState(false);
   const [totalRounds, setTotalRounds] = useState(3);
   const [roundTime, setRoundTime] = useState(80);
+  const [joinStatus, setJoinStatus] = useState<string | null>(null);
   const [players, setPlayers] = useState<{ id: string; name: string; isHost: boolean; status?: string; avatarJson?: string }[]>([
     { id: "local", name: playerName, isHost, avatarJson: getSelectedAvatarJson() },
   ]);
@@ -73,7 +77,7 @@ export function WaitingRoom({ hostChainId, playerName, isHost, onStartGame, onBa
       try {
         try {
           const res = await application.query(
-            '{ "query": "query { room { hostChainId gameState totalRounds secondsPerRound players { chainId name avatarJson status } } }" }'
+            '{ "query": "query { joinStatus room { hostChainId gameState totalRounds secondsPerRound players { chainId name avatarJson status } } }" }'
           );
           if (!aliveRef.current) return;
           const json = typeof res === 'string' ? JSON.parse(res) : res;
@@ -82,7 +86,11 @@ export function WaitingRoom({ hostChainId, playerName, isHost, onStartGame, onBa
           const isInvalid = !data || !matchesHost;
 
           if (isInvalid) {
-            if (!hasSeenRoomRef.current) return;
+            if (!hasSeenRoomRef.current) {
+              const status = json?.data?.joinStatus;
+              if (aliveRef.current) setJoinStatus(typeof status === "string" ? status : null);
+              return;
+            }
             if (pendingBackToLobbyTimeoutRef.current) return;
             pendingBackToLobbyTimeoutRef.current = window.setTimeout(async () => {
               pendingBackToLobbyTimeoutRef.current = null;
@@ -120,6 +128,7 @@ export function WaitingRoom({ hostChainId, playerName, isHost, onStartGame, onBa
           }
 
           hasSeenRoomRef.current = true;
+          setJoinStatus(null);
           if (pendingBackToLobbyTimeoutRef.current) {
             clearTimeout(pendingBackToLobbyTimeoutRef.current);
             pendingBackToLobbyTimeoutRef.current = null;
@@ -186,6 +195,15 @@ export function WaitingRoom({ hostChainId, playerName, isHost, onStartGame, onBa
     };
   }, [client, application, ready, hostChainId, playerName, isHost, totalRounds, roundTime]);
 
+  // Host only: run a block every minute so the registry keeps listing an idle public lobby
+  useEffect(() => {
+    if (!application || !ready || !isHost) return;
+    const intervalId = window.setInterval(() => {
+      application.query('{ "query": "mutation { tick }" }').catch(() => { });
+    }, LOBBY_HEARTBEAT_MS);
+    return () => clearInterval(intervalId);
+  }, [application, ready, isHost]);
+
   return (
     <div className="min-h-screen bg-white flex items-center justify-center p-4">
       <div className="w-full max-w-4xl space-y-6">
@@ -196,6 +214,7 @@ export function WaitingRoom({ hostChainId, playerName, isHost, onStartGame, onBa
           </div>
           <h1 className="text-black text-4xl">SKRIBBL</h1>
           <p className="text-black/60">Waiting for players...</p>
+          {joinStatus && <p className="text-black/60">Join request: {joinStatus}</p>}
         </div>
 
         <div className="grid md:grid-cols-2 gap-6">
@@ -207,7 +226,9 @@ export function WaitingRoom({ hostChainId, playerName, isHost, onStartGame, onBa
               </div>
               <div className="divide-y-2 divide-black">
               {players.map((player) => {
-                const isLeft = String(player.status || "").toLowerCase() === "left";
+                const status = String(player.status || "").toLowerCase();
+                const isKicked = status === "kicked";
+                const isLeft = status === "left" || isKicked;
                 return (
                 <div key={player.id} className={`px-4 py-3 flex items-center justify-between ${isLeft ? "opacity-50" : ""}`}>
                   <div className="flex items-center gap-3 min-w-0">
@@ -217,7 +238,7 @@ export function WaitingRoom({ hostChainId, playerName, isHost, onStartGame, onBa
                     />
                     <span className="truncate">
                       {player.name}
-                      {isLeft ? " (left)" : ""}
+                      {isKicked ? " (kicked)" : isLeft ? " (left)" : ""}
                     </span>
                   </div>
                   {player.isHost && (
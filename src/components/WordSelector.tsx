@@ -1,14 +1,16 @@
 interface WordSelectorProps {
   words: string[];
+  error?: string;
   onSelect: (word: string) => void;
 }
 
-export function WordSelector({ words, onSelect }: WordSelectorProps) {
+export function WordSelector({ words, error, onSelect }: WordSelectorProps) {
   return (
     <div className="flex-1 bg-white border-2 border-gray-400 rounded-lg flex items-center justify-center">
       <div className="text-center space-y-6">
         <h2 className="text-black">Choose a word to draw</h2>
         
+        {words.length === 0 && <p className="text-gray-500">Loading your words…</p>}
         <div className="flex gap-4">
           {words.map((word, idx) => (
             <button
@@ -20,6 +22,7 @@ export function WordSelector({ words, onSelect }: WordSelectorProps) {
             </button>
           ))}
         </div>
+        {error && <p className="text-red-600">{error}</p>}
       </div>
     </div>
   );